@@ -24,7 +24,7 @@ fn feldman_256(c: &mut Criterion) {
     };
     let (mut shares, mut commitments) = vss.split(&secret);
     let mut sub_shares = &shares[0..10];
-    let mut recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -37,7 +37,7 @@ fn feldman_256(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..25];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -50,7 +50,7 @@ fn feldman_256(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..50];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -63,7 +63,7 @@ fn feldman_256(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..100];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -76,7 +76,7 @@ fn feldman_256(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..128];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -92,7 +92,7 @@ fn feldman_512(c: &mut Criterion) {
     };
     let (mut shares, mut commitments) = vss.split(&secret);
     let mut sub_shares = &shares[0..10];
-    let mut recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -105,7 +105,7 @@ fn feldman_512(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..25];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -118,7 +118,7 @@ fn feldman_512(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..50];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -130,7 +130,7 @@ fn feldman_512(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..100];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -142,7 +142,7 @@ fn feldman_512(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..128];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -158,7 +158,7 @@ fn feldman_768(c: &mut Criterion) {
     };
     let (mut shares, mut commitments) = vss.split(&secret);
     let mut sub_shares = &shares[0..10];
-    let mut recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -171,7 +171,7 @@ fn feldman_768(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..25];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -184,7 +184,7 @@ fn feldman_768(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..50];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -197,7 +197,7 @@ fn feldman_768(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..100];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -210,7 +210,7 @@ fn feldman_768(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..128];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -226,7 +226,7 @@ fn feldman_1024(c: &mut Criterion) {
     };
     let (mut shares, mut commitments) = vss.split(&secret);
     let mut sub_shares = &shares[0..10];
-    let mut recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -239,7 +239,7 @@ fn feldman_1024(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..25];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -252,7 +252,7 @@ fn feldman_1024(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..50];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -265,7 +265,7 @@ fn feldman_1024(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..100];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -278,7 +278,7 @@ fn feldman_1024(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..128];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -294,7 +294,7 @@ fn feldman_1280(c: &mut Criterion) {
     };
     let (mut shares, mut commitments) = vss.split(&secret);
     let mut sub_shares = &shares[0..10];
-    let mut recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -307,7 +307,7 @@ fn feldman_1280(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..25];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -320,7 +320,7 @@ fn feldman_1280(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..50];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -333,7 +333,7 @@ fn feldman_1280(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..100];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -346,7 +346,7 @@ fn feldman_1280(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..128];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -362,7 +362,7 @@ fn feldman_1536(c: &mut Criterion) {
     };
     let (mut shares, mut commitments) = vss.split(&secret);
     let mut sub_shares = &shares[0..10];
-    let mut recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -375,7 +375,7 @@ fn feldman_1536(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..25];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -388,7 +388,7 @@ fn feldman_1536(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..50];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -401,7 +401,7 @@ fn feldman_1536(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..100];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -414,7 +414,7 @@ fn feldman_1536(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..128];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -430,7 +430,7 @@ fn feldman_1792(c: &mut Criterion) {
     };
     let (mut shares, mut commitments) = vss.split(&secret);
     let mut sub_shares = &shares[0..10];
-    let mut recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -443,7 +443,7 @@ fn feldman_1792(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..25];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -456,7 +456,7 @@ fn feldman_1792(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..50];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -469,7 +469,7 @@ fn feldman_1792(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..100];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -482,7 +482,7 @@ fn feldman_1792(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..128];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -498,7 +498,7 @@ fn feldman_2048(c: &mut Criterion) {
     };
     let (mut shares, mut commitments) = vss.split(&secret);
     let mut sub_shares = &shares[0..10];
-    let mut recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -511,7 +511,7 @@ fn feldman_2048(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..25];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -524,7 +524,7 @@ fn feldman_2048(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..50];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -537,7 +537,7 @@ fn feldman_2048(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..100];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -550,7 +550,7 @@ fn feldman_2048(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..128];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| {
             VerifiableSecretSharingRistretto::verify_all(black_box(shares.as_slice()), &commitments)
@@ -559,14 +559,14 @@ fn feldman_2048(c: &mut Criterion) {
 }
 
 fn feldman_sec_256(c: &mut Criterion) {
-    let mut secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
+    let secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
     let mut vss = VerifiableSecretSharing {
         threshold: 10,
         share_amount: 256,
     };
     let (mut shares, mut commitments) = vss.split(&secret);
     let mut sub_shares = &shares[0..10];
-    let mut recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -577,7 +577,7 @@ fn feldman_sec_256(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..25];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -588,7 +588,7 @@ fn feldman_sec_256(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..50];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -599,7 +599,7 @@ fn feldman_sec_256(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..100];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -610,21 +610,21 @@ fn feldman_sec_256(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..128];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
 }
 
 fn feldman_sec_512(c: &mut Criterion) {
-    let mut secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
+    let secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
     let mut vss = VerifiableSecretSharing {
         threshold: 10,
         share_amount: 512,
     };
     let (mut shares, mut commitments) = vss.split(&secret);
     let mut sub_shares = &shares[0..10];
-    let mut recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -635,7 +635,7 @@ fn feldman_sec_512(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..25];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -646,7 +646,7 @@ fn feldman_sec_512(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..50];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -657,7 +657,7 @@ fn feldman_sec_512(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..100];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -668,21 +668,21 @@ fn feldman_sec_512(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..128];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
 }
 
 fn feldman_sec_768(c: &mut Criterion) {
-    let mut secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
+    let secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
     let mut vss = VerifiableSecretSharing {
         threshold: 10,
         share_amount: 768,
     };
     let (mut shares, mut commitments) = vss.split(&secret);
     let mut sub_shares = &shares[0..10];
-    let mut recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -693,7 +693,7 @@ fn feldman_sec_768(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..25];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -704,7 +704,7 @@ fn feldman_sec_768(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..50];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -715,7 +715,7 @@ fn feldman_sec_768(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..100];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -726,21 +726,21 @@ fn feldman_sec_768(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..128];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
 }
 
 fn feldman_sec_1024(c: &mut Criterion) {
-    let mut secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
+    let secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
     let mut vss = VerifiableSecretSharing {
         threshold: 10,
         share_amount: 1024,
     };
     let (mut shares, mut commitments) = vss.split(&secret);
     let mut sub_shares = &shares[0..10];
-    let mut recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -751,7 +751,7 @@ fn feldman_sec_1024(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..25];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -762,7 +762,7 @@ fn feldman_sec_1024(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..50];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -773,7 +773,7 @@ fn feldman_sec_1024(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..100];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -784,21 +784,21 @@ fn feldman_sec_1024(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..128];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
 }
 
 fn feldman_sec_1280(c: &mut Criterion) {
-    let mut secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
+    let secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
     let mut vss = VerifiableSecretSharing {
         threshold: 10,
         share_amount: 1280,
     };
     let (mut shares, mut commitments) = vss.split(&secret);
     let mut sub_shares = &shares[0..10];
-    let mut recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -809,7 +809,7 @@ fn feldman_sec_1280(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..25];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -820,7 +820,7 @@ fn feldman_sec_1280(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..50];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -831,7 +831,7 @@ fn feldman_sec_1280(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..100];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -842,21 +842,21 @@ fn feldman_sec_1280(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..128];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
 }
 
 fn feldman_sec_1536(c: &mut Criterion) {
-    let mut secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
+    let secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
     let mut vss = VerifiableSecretSharing {
         threshold: 10,
         share_amount: 1536,
     };
     let (mut shares, mut commitments) = vss.split(&secret);
     let mut sub_shares = &shares[0..10];
-    let mut recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -867,7 +867,7 @@ fn feldman_sec_1536(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..25];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -878,7 +878,7 @@ fn feldman_sec_1536(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..50];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -889,7 +889,7 @@ fn feldman_sec_1536(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..100];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -900,21 +900,21 @@ fn feldman_sec_1536(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..128];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
 }
 
 fn feldman_sec_1792(c: &mut Criterion) {
-    let mut secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
+    let secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
     let mut vss = VerifiableSecretSharing {
         threshold: 10,
         share_amount: 1792,
     };
     let (mut shares, mut commitments) = vss.split(&secret);
     let mut sub_shares = &shares[0..10];
-    let mut recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -925,7 +925,7 @@ fn feldman_sec_1792(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..25];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -936,7 +936,7 @@ fn feldman_sec_1792(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..50];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -947,7 +947,7 @@ fn feldman_sec_1792(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..100];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -958,21 +958,21 @@ fn feldman_sec_1792(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..128];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
 }
 
 fn feldman_sec_2048(c: &mut Criterion) {
-    let mut secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
+    let secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
     let mut vss = VerifiableSecretSharing {
         threshold: 10,
         share_amount: 2048,
     };
     let (mut shares, mut commitments) = vss.split(&secret);
     let mut sub_shares = &shares[0..10];
-    let mut recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -983,7 +983,7 @@ fn feldman_sec_2048(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..25];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -994,7 +994,7 @@ fn feldman_sec_2048(c: &mut Criterion) {
     };
     (shares, commitments) = vss.split(&secret);
     sub_shares = &shares[0..50];
-    recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -1005,7 +1005,7 @@ fn feldman_sec_2048(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..100];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
@@ -1016,12 +1016,31 @@ fn feldman_sec_2048(c: &mut Criterion) {
     };
     let (shares, commitments) = vss.split(&secret);
     let sub_shares = &shares[0..128];
-    let recovered = vss.recover(&sub_shares);
+    assert_eq!(vss.recover(sub_shares), secret);
     c.bench_function("verify_secret", |b| {
         b.iter(|| VerifiableSecretSharing::verify_all(black_box(shares.as_slice()), &commitments))
     });
 }
 
-criterion_group!(benches, feldman_sec_2048); // change to the appropriate parameter
+// Every share amount is registered; run a subset with `cargo bench -- <filter>`.
+criterion_group!(
+    benches,
+    feldman_256,
+    feldman_512,
+    feldman_768,
+    feldman_1024,
+    feldman_1280,
+    feldman_1536,
+    feldman_1792,
+    feldman_2048,
+    feldman_sec_256,
+    feldman_sec_512,
+    feldman_sec_768,
+    feldman_sec_1024,
+    feldman_sec_1280,
+    feldman_sec_1536,
+    feldman_sec_1792,
+    feldman_sec_2048
+);
 
 criterion_main!(benches);
@@ -0,0 +1,160 @@
+use crate::field::FieldElement;
+use crate::group::GroupElement;
+use crate::lagrange::LagrangeCoefficients;
+use crate::share::{Commitments, SubShare};
+
+/// Convert a Shamir share into an additive share for the signer set
+/// `signers`: the additive shares of the signers sum up to the secret.
+/// Returns `None` if the share is not part of the signer set.
+pub fn shamir_to_additive<F: FieldElement>(share: (usize, F), signers: &[usize]) -> Option<F> {
+    let (identifier, value) = share;
+    let coefficient = LagrangeCoefficients::new(signers, F::zero()).coefficient(identifier)?;
+    Some(coefficient * value)
+}
+
+/// The public commitment `generator * w_i` to the additive share of
+/// `identifier` for the signer set `signers`, derived from the Feldman
+/// commitments of the Shamir sharing. Returns `None` if the identifier is not
+/// part of the signer set, or the commitment is the identity and the group
+/// can not represent it.
+pub fn additive_commitment<F, G>(
+    commitments: &[G],
+    identifier: usize,
+    signers: &[usize],
+) -> Option<G>
+where
+    F: FieldElement,
+    G: GroupElement<F>,
+{
+    let coefficient = shamir_to_additive((identifier, F::one()), signers)?;
+    Commitments(commitments.to_vec())
+        .checked_evaluate(identifier)?
+        .checked_mul(&coefficient)
+}
+
+/// Convert additive shares back into a fresh Shamir sharing: every holder
+/// `split`s its additive share and sends `recipient` its sub-share, and
+/// `recipient` sums the sub-shares it received. Returns the recipient's share
+/// and the commitments of the new sharing, which are the same for every
+/// recipient.
+///
+/// `commitments[0]` of a sub-share is the commitment to the dealt additive
+/// share, which can be checked against `additive_commitment`. Returns `None`
+/// if there are no sub-shares, a dealer appears twice, or a sub-share does
+/// not verify against commitments of `threshold` elements.
+pub fn additive_to_shamir<F, G>(
+    recipient: usize,
+    threshold: usize,
+    sub_shares: &[SubShare<F, G>],
+) -> Option<((usize, F), Vec<G>)>
+where
+    F: FieldElement,
+    G: GroupElement<F>,
+{
+    let mut dealers: Vec<usize> = sub_shares.iter().map(|sub| sub.dealer).collect();
+    dealers.sort_unstable();
+    dealers.dedup();
+    if dealers.len() != sub_shares.len()
+        || !sub_shares
+            .iter()
+            .all(|sub| sub.verify(recipient, threshold))
+    {
+        return None;
+    }
+    let (first, rest) = sub_shares.split_first()?;
+    let mut value = first.share;
+    let mut commitments = first.commitments.clone();
+    for sub in rest {
+        value = value.checked_add(sub.share)?;
+        commitments = commitments.checked_add(&sub.commitments)?;
+    }
+    Some((value.into(), commitments.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feldman_vss::{new_random, VerifiableSecretSharingRistretto};
+    use crate::feldman_vss_secp256k1::VerifiableSecretSharing;
+    use crate::secp256k1_helper::{Secp256k1Point, Secp256k1Scalar};
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use curve25519_dalek::scalar::Scalar;
+
+    #[test]
+    fn test_round_trip() {
+        let secret = new_random();
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: 3,
+            share_amount: 5,
+        };
+        let (shares, commitments) = vss.split(&secret);
+        let signers = [1, 3, 4];
+        let additive: Vec<Scalar> = signers
+            .iter()
+            .map(|&i| shamir_to_additive(shares[i - 1], &signers).unwrap())
+            .collect();
+        assert_eq!(additive.iter().sum::<Scalar>(), secret);
+        assert_eq!(shamir_to_additive(shares[1], &signers), None);
+
+        // Each signer reshares its additive share with a new threshold.
+        let reshare = VerifiableSecretSharingRistretto {
+            threshold: 2,
+            share_amount: 4,
+        };
+        let sub_shares: Vec<Vec<SubShare<Scalar, RistrettoPoint>>> = signers
+            .iter()
+            .zip(&additive)
+            .map(|(&i, w)| SubShare::from_split(i, reshare.split(w)))
+            .collect();
+        for (&i, dealt) in signers.iter().zip(&sub_shares) {
+            assert_eq!(
+                Some(dealt[0].commitments.0[0]),
+                additive_commitment(&commitments, i, &signers)
+            );
+        }
+        // Party j receives the j-th sub-share of every dealer.
+        let received = |recipient: usize| -> Vec<SubShare<Scalar, RistrettoPoint>> {
+            sub_shares
+                .iter()
+                .map(|dealt| dealt[recipient - 1].clone())
+                .collect()
+        };
+        let (new_shares, new_commitments): (Vec<_>, Vec<_>) = (1..=4)
+            .map(|recipient| additive_to_shamir(recipient, 2, &received(recipient)).unwrap())
+            .unzip();
+        assert_eq!(new_commitments[0][0], commitments[0]);
+        assert!(VerifiableSecretSharingRistretto::verify_all(
+            &new_shares,
+            &new_commitments[3]
+        ));
+        assert_eq!(reshare.recover(&new_shares[2..]), secret);
+
+        let mut forged = received(3);
+        forged[2].share.value += Scalar::one();
+        assert_eq!(additive_to_shamir(3, 2, &forged), None);
+        assert_eq!(additive_to_shamir(4, 2, &received(3)), None);
+        assert_eq!(additive_to_shamir(3, 3, &received(3)), None);
+        assert_eq!(
+            additive_to_shamir::<Scalar, RistrettoPoint>(3, 2, &[]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_secp256k1() {
+        let secret = Secp256k1Scalar::new_random();
+        let vss = VerifiableSecretSharing {
+            threshold: 2,
+            share_amount: 3,
+        };
+        let (shares, commitments) = vss.split(&secret);
+        let signers = [3, 2];
+        let additive: Vec<Secp256k1Scalar> = signers
+            .iter()
+            .map(|&i| shamir_to_additive(shares[i - 1], &signers).unwrap())
+            .collect();
+        assert_eq!(additive[0] + additive[1], secret);
+        let commitment: Option<Secp256k1Point> = additive_commitment(&commitments, 3, &signers);
+        assert_eq!(commitment, Some(Secp256k1Point::generator() * additive[0]));
+    }
+}
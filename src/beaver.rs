@@ -0,0 +1,236 @@
+use crate::feldman_vss::{new_random, VerifiableSecretSharingRistretto};
+use crate::proofs::DleqProof;
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+
+/// A party's shares of a Beaver triple `(a, b, c = a * b)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BeaverTriple {
+    /// the share of `a`.
+    pub a: (usize, Scalar),
+    /// the share of `b`.
+    pub b: (usize, Scalar),
+    /// the share of `c`.
+    pub c: (usize, Scalar),
+}
+
+/// The public commitments of a Beaver triple.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BeaverTripleCommitments {
+    /// the commitments of the sharing of `a`.
+    pub a: Vec<RistrettoPoint>,
+    /// the commitments of the sharing of `b`.
+    pub b: Vec<RistrettoPoint>,
+    /// the commitments of the sharing of `c`.
+    pub c: Vec<RistrettoPoint>,
+    /// the DLEQ proof that `log_g a[0] == log_{b[0]} c[0]`, i.e. `c = a * b`.
+    pub proof: DleqProof<Scalar, RistrettoPoint>,
+}
+
+/// The `BeaverMultiplicationRistretto` structure, for multiplication of
+/// values shared with `VerifiableSecretSharingRistretto` with preprocessed
+/// Beaver triples.
+///
+/// Online, the parties open `d = x - a` and `e = y - b` for all their
+/// multiplications at once, then compute their shares of
+/// `x * y = c + d * b + e * a + d * e` locally. Triples come from a dealer
+/// with `deal`, or without one by sharing random `a` and `b` jointly and
+/// computing `c` with `BgwMultiplicationRistretto`.
+pub struct BeaverMultiplicationRistretto {
+    /// the threshold of shares.
+    pub threshold: usize,
+    /// the total number of shares.
+    pub share_amount: usize,
+}
+
+impl BeaverMultiplicationRistretto {
+    /// Deal a random triple, one `BeaverTriple` per party.
+    pub fn deal(&self) -> (Vec<BeaverTriple>, BeaverTripleCommitments) {
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: self.threshold,
+            share_amount: self.share_amount,
+        };
+        let (a, b) = (new_random(), new_random());
+        let (a_shares, a_commitments) = vss.split(&a);
+        let (b_shares, b_commitments) = vss.split(&b);
+        let (c_shares, c_commitments) = vss.split(&(a * b));
+        let proof = DleqProof::prove(
+            &mut Self::transcript(),
+            &RISTRETTO_BASEPOINT_POINT,
+            &a_commitments[0],
+            &b_commitments[0],
+            &c_commitments[0],
+            &a,
+        );
+        let triples = a_shares
+            .into_iter()
+            .zip(b_shares)
+            .zip(c_shares)
+            .map(|((a, b), c)| BeaverTriple { a, b, c })
+            .collect();
+        let commitments = BeaverTripleCommitments {
+            a: a_commitments,
+            b: b_commitments,
+            c: c_commitments,
+            proof,
+        };
+        (triples, commitments)
+    }
+
+    /// Verify that the committed triple satisfies `c = a * b`, and that a
+    /// party's shares are valid. Returns false unless each sharing has
+    /// `threshold` commitments.
+    pub fn verify_triple(
+        &self,
+        triple: &BeaverTriple,
+        commitments: &BeaverTripleCommitments,
+    ) -> bool {
+        [&commitments.a, &commitments.b, &commitments.c]
+            .iter()
+            .all(|sharing| sharing.len() == self.threshold)
+            && commitments.proof.verify(
+                &mut Self::transcript(),
+                &RISTRETTO_BASEPOINT_POINT,
+                &commitments.a[0],
+                &commitments.b[0],
+                &commitments.c[0],
+            )
+            && VerifiableSecretSharingRistretto::verify(triple.a, &commitments.a)
+            && VerifiableSecretSharingRistretto::verify(triple.b, &commitments.b)
+            && VerifiableSecretSharingRistretto::verify(triple.c, &commitments.c)
+    }
+
+    /// Mask a party's shares of `x` and `y` with a triple, giving its shares
+    /// of `d = x - a` and `e = y - b` to broadcast.
+    pub fn mask(
+        x_share: (usize, Scalar),
+        y_share: (usize, Scalar),
+        triple: &BeaverTriple,
+    ) -> ((usize, Scalar), (usize, Scalar)) {
+        let identifier = x_share.0;
+        assert!(y_share.0 == identifier && triple.a.0 == identifier);
+        (
+            (identifier, x_share.1 - triple.a.1),
+            (identifier, y_share.1 - triple.b.1),
+        )
+    }
+
+    /// Open `d` or `e` from threshold broadcast shares.
+    pub fn open(&self, masked: &[(usize, Scalar)]) -> Scalar {
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: self.threshold,
+            share_amount: self.share_amount,
+        };
+        vss.recover(masked)
+    }
+
+    /// Compute a party's share of `x * y` from its triple and the opened `d`
+    /// and `e`.
+    pub fn multiply(triple: &BeaverTriple, d: &Scalar, e: &Scalar) -> (usize, Scalar) {
+        let (identifier, c) = triple.c;
+        (identifier, c + d * triple.b.1 + e * triple.a.1 + d * e)
+    }
+
+    /// Compute the commitments of the sharing of `x * y`, matching `multiply`.
+    pub fn multiply_commitments(
+        commitments: &BeaverTripleCommitments,
+        d: &Scalar,
+        e: &Scalar,
+    ) -> Vec<RistrettoPoint> {
+        let mut product: Vec<RistrettoPoint> = commitments
+            .c
+            .iter()
+            .zip(&commitments.b)
+            .zip(&commitments.a)
+            .map(|((c, b), a)| c + b * d + a * e)
+            .collect();
+        product[0] += RISTRETTO_BASEPOINT_POINT * (d * e);
+        product
+    }
+
+    fn transcript() -> Transcript {
+        Transcript::new(b"secret-sharing-extra beaver triple")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integration() {
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: 3,
+            share_amount: 5,
+        };
+        let beaver = BeaverMultiplicationRistretto {
+            threshold: 3,
+            share_amount: 5,
+        };
+        let (x, y) = (new_random(), new_random());
+        let (x_shares, x_commitments) = vss.split(&x);
+        let (y_shares, y_commitments) = vss.split(&y);
+        let (triples, commitments) = beaver.deal();
+        assert!(triples
+            .iter()
+            .all(|triple| beaver.verify_triple(triple, &commitments)));
+
+        let (d_shares, e_shares): (Vec<_>, Vec<_>) = x_shares
+            .iter()
+            .zip(&y_shares)
+            .zip(&triples)
+            .map(|((&xi, &yi), triple)| BeaverMultiplicationRistretto::mask(xi, yi, triple))
+            .unzip();
+        // The broadcast shares are verifiable against the public commitments.
+        let masked_commitments = |values: &[RistrettoPoint], masks: &[RistrettoPoint]| {
+            values
+                .iter()
+                .zip(masks)
+                .map(|(value, mask)| value - mask)
+                .collect::<Vec<RistrettoPoint>>()
+        };
+        assert!(VerifiableSecretSharingRistretto::verify_all(
+            &d_shares,
+            &masked_commitments(&x_commitments, &commitments.a)
+        ));
+        assert!(VerifiableSecretSharingRistretto::verify_all(
+            &e_shares,
+            &masked_commitments(&y_commitments, &commitments.b)
+        ));
+        let (d, e) = (beaver.open(&d_shares[..3]), beaver.open(&e_shares[2..]));
+
+        let product_shares: Vec<(usize, Scalar)> = triples
+            .iter()
+            .map(|triple| BeaverMultiplicationRistretto::multiply(triple, &d, &e))
+            .collect();
+        let product_commitments =
+            BeaverMultiplicationRistretto::multiply_commitments(&commitments, &d, &e);
+        assert!(VerifiableSecretSharingRistretto::verify_all(
+            &product_shares,
+            &product_commitments
+        ));
+        assert_eq!(vss.recover(&product_shares[1..4]), x * y);
+    }
+
+    #[test]
+    fn test_bad_dealer() {
+        let beaver = BeaverMultiplicationRistretto {
+            threshold: 2,
+            share_amount: 3,
+        };
+        let (triples, commitments) = beaver.deal();
+        assert!(beaver.verify_triple(&triples[0], &commitments));
+
+        let mut empty = commitments.clone();
+        empty.c.clear();
+        assert!(!beaver.verify_triple(&triples[0], &empty));
+        let mut long = commitments.clone();
+        long.a.push(RISTRETTO_BASEPOINT_POINT);
+        assert!(!beaver.verify_triple(&triples[0], &long));
+        let mut short = commitments;
+        short.b.truncate(1);
+        assert!(!beaver.verify_triple(&triples[0], &short));
+    }
+}
@@ -0,0 +1,206 @@
+use crate::feldman_vss::VerifiableSecretSharingRistretto;
+use crate::lagrange::LagrangeCoefficients;
+use crate::proofs::DleqProof;
+use crate::share::SubShare;
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+
+/// The public part of a party's resharing of the product `x_i * y_i` of its
+/// shares, broadcast to every party. The sub-shares are sent privately.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BgwReshare {
+    /// the identifier of the resharing party.
+    pub dealer: usize,
+    /// the commitments of the resharing.
+    pub commitments: Vec<RistrettoPoint>,
+    /// the DLEQ proof that `log_g (g * x_i) == log_{g * y_i} commitments[0]`,
+    /// i.e. that the reshared value is the product of the party's shares.
+    pub proof: DleqProof<Scalar, RistrettoPoint>,
+}
+
+/// The `BgwMultiplicationRistretto` structure, for BGW multiplication of two
+/// values shared with `VerifiableSecretSharingRistretto`.
+///
+/// The local products `x_i * y_i` lie on a polynomial of degree
+/// `2 * threshold - 2`; every party reshares its product, and the sub-shares
+/// are recombined with the Lagrange coefficients of the dealers, giving a
+/// sharing of `x * y` with the original threshold. Requires
+/// `share_amount >= 2 * threshold - 1`.
+pub struct BgwMultiplicationRistretto {
+    /// the threshold of shares.
+    pub threshold: usize,
+    /// the total number of shares.
+    pub share_amount: usize,
+}
+
+impl BgwMultiplicationRistretto {
+    /// Reshare the product of a party's shares of `x` and `y`. Returns the
+    /// public resharing and the sub-shares of `x_i * y_i`, of which
+    /// `sub_shares[j - 1]` is sent privately to party `j`.
+    pub fn reshare(
+        &self,
+        x_share: (usize, Scalar),
+        y_share: (usize, Scalar),
+    ) -> (BgwReshare, Vec<SubShare<Scalar, RistrettoPoint>>) {
+        assert!(self.share_amount + 1 >= 2 * self.threshold);
+        assert!(x_share.0 == y_share.0);
+
+        let (dealer, x) = x_share;
+        let y = y_share.1;
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: self.threshold,
+            share_amount: self.share_amount,
+        };
+        let (shares, commitments) = vss.split(&(x * y));
+        let generator = RISTRETTO_BASEPOINT_POINT;
+        let proof = DleqProof::prove(
+            &mut Self::transcript(dealer),
+            &generator,
+            &(generator * x),
+            &(generator * y),
+            &commitments[0],
+            &x,
+        );
+        let reshare = BgwReshare {
+            dealer,
+            commitments: commitments.clone(),
+            proof,
+        };
+        (reshare, SubShare::from_split(dealer, (shares, commitments)))
+    }
+
+    /// Verify that a resharing is of the product of the dealer's shares, given
+    /// the commitments of the sharings of `x` and `y`. Returns false if either
+    /// sharing has no commitments.
+    pub fn verify_reshare(
+        &self,
+        reshare: &BgwReshare,
+        x_commitments: &[RistrettoPoint],
+        y_commitments: &[RistrettoPoint],
+    ) -> bool {
+        reshare.commitments.len() == self.threshold
+            && !x_commitments.is_empty()
+            && !y_commitments.is_empty()
+            && reshare.proof.verify(
+                &mut Self::transcript(reshare.dealer),
+                &RISTRETTO_BASEPOINT_POINT,
+                &VerifiableSecretSharingRistretto::evaluate_commitments(
+                    x_commitments,
+                    reshare.dealer,
+                ),
+                &VerifiableSecretSharingRistretto::evaluate_commitments(
+                    y_commitments,
+                    reshare.dealer,
+                ),
+                &reshare.commitments[0],
+            )
+    }
+
+    /// Combine the sub-shares party `recipient` received from `2 * threshold - 1`
+    /// dealers into its share of `x * y`, and the commitments of the new
+    /// sharing. The commitments of each sub-share must be those of its
+    /// dealer's verified `BgwReshare`.
+    ///
+    /// Returns `None` if the number of dealers is wrong, a dealer appears
+    /// twice, or a sub-share does not verify against its dealer's commitments.
+    pub fn combine(
+        &self,
+        recipient: usize,
+        sub_shares: &[SubShare<Scalar, RistrettoPoint>],
+    ) -> Option<((usize, Scalar), Vec<RistrettoPoint>)> {
+        let dealers: Vec<usize> = sub_shares.iter().map(|sub| sub.dealer).collect();
+        let mut distinct = dealers.clone();
+        distinct.sort_unstable();
+        distinct.dedup();
+        if sub_shares.len() + 1 != 2 * self.threshold
+            || distinct.len() != dealers.len()
+            || !sub_shares
+                .iter()
+                .all(|sub| sub.verify(recipient, self.threshold))
+        {
+            return None;
+        }
+
+        let coefficients = LagrangeCoefficients::new(&dealers, Scalar::zero());
+        let mut value = Scalar::zero();
+        let mut commitments = vec![RistrettoPoint::default(); self.threshold];
+        for (sub, coefficient) in sub_shares.iter().zip(coefficients.coefficients()) {
+            value += coefficient * sub.share.value;
+            for (commitment, item) in commitments.iter_mut().zip(&sub.commitments.0) {
+                *commitment += item * coefficient;
+            }
+        }
+        Some(((recipient, value), commitments))
+    }
+
+    fn transcript(dealer: usize) -> Transcript {
+        let mut transcript = Transcript::new(b"secret-sharing-extra bgw multiplication");
+        transcript.append_u64(b"dealer", dealer as u64);
+        transcript
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feldman_vss::new_random;
+
+    #[test]
+    fn test_integration() {
+        let (x, y) = (new_random(), new_random());
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: 3,
+            share_amount: 6,
+        };
+        let (x_shares, x_commitments) = vss.split(&x);
+        let (y_shares, y_commitments) = vss.split(&y);
+        let bgw = BgwMultiplicationRistretto {
+            threshold: 3,
+            share_amount: 6,
+        };
+
+        let (reshares, sub_shares): (Vec<BgwReshare>, Vec<_>) = x_shares
+            .iter()
+            .zip(&y_shares)
+            .map(|(&xi, &yi)| bgw.reshare(xi, yi))
+            .unzip();
+        assert!(reshares.iter().all(|reshare| bgw.verify_reshare(
+            reshare,
+            &x_commitments,
+            &y_commitments
+        )));
+        let (cheating, _) = bgw.reshare((1, x_shares[0].1 + Scalar::one()), y_shares[0]);
+        assert!(!bgw.verify_reshare(&cheating, &x_commitments, &y_commitments));
+        assert!(!bgw.verify_reshare(&reshares[0], &[], &y_commitments));
+        assert!(!bgw.verify_reshare(&reshares[0], &x_commitments, &[]));
+
+        // Party j receives the j-th sub-share of every dealer. Any 2t - 1 = 5
+        // dealers suffice.
+        let received = |recipient: usize| -> Vec<SubShare<Scalar, RistrettoPoint>> {
+            sub_shares[1..]
+                .iter()
+                .map(|dealt| dealt[recipient - 1].clone())
+                .collect()
+        };
+        let combined: Vec<((usize, Scalar), Vec<RistrettoPoint>)> = (1..=6)
+            .map(|recipient| bgw.combine(recipient, &received(recipient)).unwrap())
+            .collect();
+        let (product_shares, commitments): (Vec<_>, Vec<_>) = combined.into_iter().unzip();
+        assert!(commitments.windows(2).all(|pair| pair[0] == pair[1]));
+        assert!(VerifiableSecretSharingRistretto::verify_all(
+            &product_shares,
+            &commitments[0]
+        ));
+        assert_eq!(vss.recover(&product_shares[3..]), x * y);
+
+        let mut forged = received(2);
+        forged[0].share.value += Scalar::one();
+        assert_eq!(bgw.combine(2, &forged), None);
+        assert_eq!(bgw.combine(3, &received(2)), None);
+        let mut repeated = received(2);
+        repeated[1] = repeated[0].clone();
+        assert_eq!(bgw.combine(2, &repeated), None);
+    }
+}
@@ -0,0 +1,592 @@
+use crate::beaver::{BeaverMultiplicationRistretto, BeaverTriple};
+use crate::feldman_vss::VerifiableSecretSharingRistretto;
+use crate::joint_random::JointShare;
+use curve25519_dalek::scalar::Scalar;
+use rand::{thread_rng, Rng};
+
+/// The statistical security, in bits, of the masking of the compared values.
+const STATISTICAL_SECURITY: usize = 40;
+
+/// A party's preprocessed material for one comparison: the shares of a random
+/// mask `r = sum(2^i * bits[i]) + 2^bit_length * high` and of the Beaver
+/// triples of the bitwise comparison.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComparisonMask {
+    /// the shares of the low bits of the mask, least significant first.
+    pub bits: Vec<(usize, Scalar)>,
+    /// the share of the high part of the mask, of `STATISTICAL_SECURITY + 1`
+    /// bits.
+    pub high: (usize, Scalar),
+    /// the `2 * bit_length` Beaver triples.
+    pub triples: Vec<BeaverTriple>,
+}
+
+/// The next step of a `ComparisonProtocol`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ComparisonStep {
+    /// Broadcast these shares, open every value from threshold parties, and
+    /// pass the opened values, in order, to the next `step`.
+    Open(Vec<(usize, Scalar)>),
+    /// The party's share of the result, `0` or `1`.
+    Done((usize, Scalar)),
+}
+
+/// The `SecureComparisonRistretto` structure, for computing sharings of
+/// `[a < b]` and `[a == b]` from sharings of `a` and `b` made with
+/// `VerifiableSecretSharingRistretto`, without reconstructing them.
+///
+/// The values must lie in `[0, 2^bit_length)`. The parties open
+/// `c = a - b + 2^bit_length + r` for a random mask `r` with shared bits, which
+/// hides `a - b` statistically, and then compare `c` with the bits of `r` in
+/// `bit_length` rounds of Beaver multiplications. Equality is
+/// `1 - [a < b] - [b < a]`. The protocol is secure against semi-honest
+/// parties.
+///
+/// Without a dealer, each party builds its masks with `mask_from_random_bits`
+/// from bits of `JointRandomRistretto::random_bit`, with Beaver triples made
+/// as described in `BeaverMultiplicationRistretto`. `deal_mask_trusted` is a
+/// trusted-setup variant, in which a dealer who learns every mask makes them.
+///
+/// `bit_length` must be positive and leave room for the masking in the group
+/// order: `bit_length + STATISTICAL_SECURITY + 2 < 252`.
+pub struct SecureComparisonRistretto {
+    /// the threshold of shares.
+    pub threshold: usize,
+    /// the total number of shares.
+    pub share_amount: usize,
+    /// the bit length of the compared values.
+    pub bit_length: usize,
+}
+
+/// A party's state in a comparison or equality test.
+pub struct ComparisonProtocol {
+    bit_length: usize,
+    equality: bool,
+    lanes: Vec<Lane>,
+    state: State,
+}
+
+/// A single `[a < b]` computation.
+struct Lane {
+    identifier: usize,
+    z: Scalar,
+    mask: ComparisonMask,
+    c_low: Scalar,
+    c_bits: Vec<bool>,
+    equal_prefix: Scalar,
+    less_than: Scalar,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    Start,
+    Masked,
+    Bit(usize),
+    Done,
+}
+
+impl SecureComparisonRistretto {
+    /// Deal the masks of one comparison, one `ComparisonMask` per party.
+    ///
+    /// This is the trusted-setup variant: the dealer learns the masks, and
+    /// with them the compared values, so it must not collude with any party.
+    pub fn deal_mask_trusted(&self) -> Vec<ComparisonMask> {
+        self.check_bit_length();
+
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: self.threshold,
+            share_amount: self.share_amount,
+        };
+        let beaver = BeaverMultiplicationRistretto {
+            threshold: self.threshold,
+            share_amount: self.share_amount,
+        };
+        let mut rng = thread_rng();
+        let bits: Vec<Vec<(usize, Scalar)>> = (0..self.bit_length)
+            .map(|_| vss.split(&Scalar::from(rng.gen::<bool>() as u64)).0)
+            .collect();
+        let high = rng.gen::<u64>() & ((1 << (STATISTICAL_SECURITY + 1)) - 1);
+        let (high_shares, _) = vss.split(&Scalar::from(high));
+        let triples: Vec<Vec<BeaverTriple>> =
+            (0..2 * self.bit_length).map(|_| beaver.deal().0).collect();
+        (0..self.share_amount)
+            .map(|j| ComparisonMask {
+                bits: bits.iter().map(|shares| shares[j]).collect(),
+                high: high_shares[j],
+                triples: triples.iter().map(|triples| triples[j]).collect(),
+            })
+            .collect()
+    }
+
+    /// Build a party's mask of one comparison from its shares of
+    /// `bit_length` random bits for the low part, `STATISTICAL_SECURITY + 1`
+    /// random bits for the high part, and `2 * bit_length` Beaver triples.
+    /// Returns `None` if a count is wrong or the shares are of different
+    /// parties.
+    pub fn mask_from_random_bits(
+        &self,
+        bits: &[JointShare],
+        high_bits: &[JointShare],
+        triples: Vec<BeaverTriple>,
+    ) -> Option<ComparisonMask> {
+        self.check_bit_length();
+        let identifier = bits.first()?.share.identifier;
+        if bits.len() != self.bit_length
+            || high_bits.len() != STATISTICAL_SECURITY + 1
+            || triples.len() != 2 * self.bit_length
+            || bits
+                .iter()
+                .chain(high_bits)
+                .any(|bit| bit.share.identifier != identifier)
+            || triples
+                .iter()
+                .any(|triple| !is_triple_of(triple, identifier))
+        {
+            return None;
+        }
+        let high = high_bits
+            .iter()
+            .rev()
+            .fold(Scalar::zero(), |sum, bit| sum + sum + bit.share.value);
+        Some(ComparisonMask {
+            bits: bits.iter().map(|bit| bit.share.into()).collect(),
+            high: (identifier, high),
+            triples,
+        })
+    }
+
+    /// Start the computation of a party's share of `[a < b]`. Returns `None`
+    /// if the shares and the mask are of different parties, or the mask has
+    /// the wrong size.
+    pub fn less_than(
+        &self,
+        a_share: (usize, Scalar),
+        b_share: (usize, Scalar),
+        mask: ComparisonMask,
+    ) -> Option<ComparisonProtocol> {
+        Some(ComparisonProtocol {
+            bit_length: self.bit_length,
+            equality: false,
+            lanes: vec![self.lane(a_share, b_share, mask)?],
+            state: State::Start,
+        })
+    }
+
+    /// Start the computation of a party's share of `[a == b]`, with the masks
+    /// of two comparisons. Returns `None` as `less_than` does.
+    pub fn equal(
+        &self,
+        a_share: (usize, Scalar),
+        b_share: (usize, Scalar),
+        masks: (ComparisonMask, ComparisonMask),
+    ) -> Option<ComparisonProtocol> {
+        Some(ComparisonProtocol {
+            bit_length: self.bit_length,
+            equality: true,
+            lanes: vec![
+                self.lane(a_share, b_share, masks.0)?,
+                self.lane(b_share, a_share, masks.1)?,
+            ],
+            state: State::Start,
+        })
+    }
+
+    fn check_bit_length(&self) {
+        assert!(self.bit_length > 0 && self.bit_length + STATISTICAL_SECURITY + 2 < 252);
+    }
+
+    fn lane(
+        &self,
+        a_share: (usize, Scalar),
+        b_share: (usize, Scalar),
+        mask: ComparisonMask,
+    ) -> Option<Lane> {
+        self.check_bit_length();
+        let identifier = a_share.0;
+        if b_share.0 != identifier
+            || mask.high.0 != identifier
+            || mask.bits.len() != self.bit_length
+            || mask.triples.len() != 2 * self.bit_length
+            || mask.bits.iter().any(|bit| bit.0 != identifier)
+            || mask
+                .triples
+                .iter()
+                .any(|triple| !is_triple_of(triple, identifier))
+        {
+            return None;
+        }
+        Some(Lane {
+            identifier,
+            z: a_share.1 - b_share.1 + power_of_two(self.bit_length),
+            mask,
+            c_low: Scalar::zero(),
+            c_bits: vec![],
+            equal_prefix: Scalar::one(),
+            less_than: Scalar::zero(),
+        })
+    }
+}
+
+impl ComparisonProtocol {
+    /// Advance the protocol with the values opened in the previous step; the
+    /// first call takes no values.
+    pub fn step(&mut self, opened: &[Scalar]) -> ComparisonStep {
+        match self.state {
+            State::Start => {
+                assert!(opened.is_empty());
+                self.state = State::Masked;
+                let k = power_of_two(self.bit_length);
+                ComparisonStep::Open(
+                    self.lanes
+                        .iter()
+                        .map(|lane| {
+                            (
+                                lane.identifier,
+                                lane.z + lane.mask_low() + k * lane.mask.high.1,
+                            )
+                        })
+                        .collect(),
+                )
+            }
+            State::Masked => {
+                assert!(opened.len() == self.lanes.len());
+                for (lane, c) in self.lanes.iter_mut().zip(opened) {
+                    let bytes = c.to_bytes();
+                    lane.c_bits = (0..self.bit_length)
+                        .map(|i| (bytes[i / 8] >> (i % 8)) & 1 == 1)
+                        .collect();
+                    lane.c_low = lane.c_bits.iter().rev().fold(Scalar::zero(), |sum, &bit| {
+                        sum + sum + Scalar::from(bit as u64)
+                    });
+                }
+                self.multiply_bit(self.bit_length - 1)
+            }
+            State::Bit(i) => {
+                assert!(opened.len() == 4 * self.lanes.len());
+                for (lane, opened) in self.lanes.iter_mut().zip(opened.chunks(4)) {
+                    let triples = &lane.mask.triples[2 * i..];
+                    lane.less_than += BeaverMultiplicationRistretto::multiply(
+                        &triples[0],
+                        &opened[0],
+                        &opened[1],
+                    )
+                    .1;
+                    lane.equal_prefix = BeaverMultiplicationRistretto::multiply(
+                        &triples[1],
+                        &opened[2],
+                        &opened[3],
+                    )
+                    .1;
+                }
+                if i > 0 {
+                    return self.multiply_bit(i - 1);
+                }
+                self.state = State::Done;
+                let results: Vec<Scalar> = self
+                    .lanes
+                    .iter()
+                    .map(|lane| lane.result(self.bit_length))
+                    .collect();
+                let result = if self.equality {
+                    Scalar::one() - results[0] - results[1]
+                } else {
+                    results[0]
+                };
+                ComparisonStep::Done((self.lanes[0].identifier, result))
+            }
+            State::Done => panic!("the comparison is done"),
+        }
+    }
+
+    /// Mask the two multiplications of bit `i`, from the most significant:
+    /// `less_than += equal_prefix * [r_i > c_i]` and
+    /// `equal_prefix *= [r_i == c_i]`.
+    fn multiply_bit(&mut self, i: usize) -> ComparisonStep {
+        self.state = State::Bit(i);
+        let mut shares = vec![];
+        for lane in &self.lanes {
+            let (identifier, r) = lane.mask.bits[i];
+            let c = Scalar::from(lane.c_bits[i] as u64);
+            let greater = r * (Scalar::one() - c);
+            let same = Scalar::one() - (c + r - (c + c) * r);
+            let prefix = (identifier, lane.equal_prefix);
+            let triples = &lane.mask.triples[2 * i..];
+            let (d, e) =
+                BeaverMultiplicationRistretto::mask(prefix, (identifier, greater), &triples[0]);
+            shares.extend_from_slice(&[d, e]);
+            let (d, e) =
+                BeaverMultiplicationRistretto::mask(prefix, (identifier, same), &triples[1]);
+            shares.extend_from_slice(&[d, e]);
+        }
+        ComparisonStep::Open(shares)
+    }
+}
+
+impl Lane {
+    fn mask_low(&self) -> Scalar {
+        self.mask
+            .bits
+            .iter()
+            .rev()
+            .fold(Scalar::zero(), |sum, bit| sum + sum + bit.1)
+    }
+
+    /// `[a < b] = 1 - (z - (z mod 2^k)) / 2^k`, where
+    /// `z mod 2^k = c_low - r_low + 2^k * [c_low < r_low]`.
+    fn result(&self, bit_length: usize) -> Scalar {
+        let inverse = power_of_two(bit_length).invert();
+        Scalar::one() - (self.z - self.c_low + self.mask_low()) * inverse + self.less_than
+    }
+}
+
+/// Whether every share of the triple is of the party `identifier`.
+fn is_triple_of(triple: &BeaverTriple, identifier: usize) -> bool {
+    triple.a.0 == identifier && triple.b.0 == identifier && triple.c.0 == identifier
+}
+
+fn power_of_two(exponent: usize) -> Scalar {
+    (0..exponent).fold(Scalar::one(), |power, _| power + power)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bgw::BgwMultiplicationRistretto;
+    use crate::joint_random::JointRandomRistretto;
+    use crate::share::SubShare;
+    use curve25519_dalek::ristretto::RistrettoPoint;
+
+    fn run(parties: &mut [ComparisonProtocol], vss: &VerifiableSecretSharingRistretto) -> Scalar {
+        let mut opened = vec![];
+        loop {
+            let steps: Vec<ComparisonStep> = parties
+                .iter_mut()
+                .map(|party| party.step(&opened))
+                .collect();
+            let shares: Vec<Vec<(usize, Scalar)>> = steps
+                .into_iter()
+                .map(|step| match step {
+                    ComparisonStep::Open(shares) => shares,
+                    ComparisonStep::Done(share) => vec![share],
+                })
+                .collect();
+            opened = (0..shares[0].len())
+                .map(|i| {
+                    let values: Vec<(usize, Scalar)> = shares[1..=vss.threshold]
+                        .iter()
+                        .map(|shares| shares[i])
+                        .collect();
+                    vss.recover(&values)
+                })
+                .collect();
+            if parties[0].state == State::Done {
+                return opened[0];
+            }
+        }
+    }
+
+    /// Generate one random bit per party with `JointRandomRistretto` and BGW.
+    fn random_bits(threshold: usize, share_amount: usize) -> Vec<JointShare> {
+        let joint = JointRandomRistretto {
+            threshold,
+            share_amount,
+        };
+        let bgw = BgwMultiplicationRistretto {
+            threshold,
+            share_amount,
+        };
+        let vss = VerifiableSecretSharingRistretto {
+            threshold,
+            share_amount,
+        };
+        loop {
+            let dealt: Vec<Vec<SubShare<Scalar, RistrettoPoint>>> = (1..=share_amount)
+                .map(|dealer| joint.deal(dealer))
+                .collect();
+            let received = |dealt: &[Vec<SubShare<Scalar, RistrettoPoint>>], recipient: usize| {
+                dealt
+                    .iter()
+                    .map(|dealt| dealt[recipient - 1].clone())
+                    .collect::<Vec<_>>()
+            };
+            let r: Vec<JointShare> = (1..=share_amount)
+                .map(|recipient| {
+                    joint
+                        .combine(recipient, &received(&dealt, recipient))
+                        .unwrap()
+                })
+                .collect();
+            let sub_shares: Vec<Vec<SubShare<Scalar, RistrettoPoint>>> = r
+                .iter()
+                .map(|r| bgw.reshare(r.share.into(), r.share.into()).1)
+                .collect();
+            let squares: Vec<(usize, Scalar)> = (1..=share_amount)
+                .map(|recipient| {
+                    bgw.combine(recipient, &received(&sub_shares, recipient))
+                        .unwrap()
+                        .0
+                })
+                .collect();
+            let square = vss.recover(&squares[..threshold]);
+            if let Some(bits) = r
+                .iter()
+                .map(|r| JointRandomRistretto::random_bit(r, &square))
+                .collect()
+            {
+                return bits;
+            }
+        }
+    }
+
+    #[test]
+    fn test_comparison() {
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: 2,
+            share_amount: 4,
+        };
+        let comparison = SecureComparisonRistretto {
+            threshold: 2,
+            share_amount: 4,
+            bit_length: 8,
+        };
+        for &(a, b) in &[(3u64, 200u64), (200, 3), (77, 77), (0, 255), (0, 0)] {
+            let (a_shares, _) = vss.split(&Scalar::from(a));
+            let (b_shares, _) = vss.split(&Scalar::from(b));
+
+            let masks = comparison.deal_mask_trusted();
+            let mut parties: Vec<ComparisonProtocol> = a_shares
+                .iter()
+                .zip(&b_shares)
+                .zip(masks)
+                .map(|((&ai, &bi), mask)| comparison.less_than(ai, bi, mask).unwrap())
+                .collect();
+            assert_eq!(run(&mut parties, &vss), Scalar::from((a < b) as u64));
+
+            let masks = comparison
+                .deal_mask_trusted()
+                .into_iter()
+                .zip(comparison.deal_mask_trusted());
+            let mut parties: Vec<ComparisonProtocol> = a_shares
+                .iter()
+                .zip(&b_shares)
+                .zip(masks)
+                .map(|((&ai, &bi), masks)| comparison.equal(ai, bi, masks).unwrap())
+                .collect();
+            assert_eq!(run(&mut parties, &vss), Scalar::from((a == b) as u64));
+        }
+    }
+
+    #[test]
+    fn test_mask_from_random_bits() {
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: 2,
+            share_amount: 3,
+        };
+        let comparison = SecureComparisonRistretto {
+            threshold: 2,
+            share_amount: 3,
+            bit_length: 4,
+        };
+        let beaver = BeaverMultiplicationRistretto {
+            threshold: 2,
+            share_amount: 3,
+        };
+        // bits[i][j] is party j's share of the i-th random bit.
+        let bits: Vec<Vec<JointShare>> = (0..comparison.bit_length + STATISTICAL_SECURITY + 1)
+            .map(|_| random_bits(2, 3))
+            .collect();
+        let triples: Vec<Vec<BeaverTriple>> = (0..2 * comparison.bit_length)
+            .map(|_| beaver.deal().0)
+            .collect();
+        let masks: Vec<ComparisonMask> = (0..3)
+            .map(|j| {
+                let party: Vec<JointShare> = bits.iter().map(|bits| bits[j].clone()).collect();
+                let party_triples: Vec<BeaverTriple> =
+                    triples.iter().map(|triples| triples[j]).collect();
+                assert!(comparison
+                    .mask_from_random_bits(&party[1..], &party[..1], party_triples.clone())
+                    .is_none());
+                let mut foreign = party_triples.clone();
+                foreign[0].b = triples[0][(j + 1) % 3].b;
+                assert!(comparison
+                    .mask_from_random_bits(
+                        &party[..comparison.bit_length],
+                        &party[comparison.bit_length..],
+                        foreign,
+                    )
+                    .is_none());
+                comparison
+                    .mask_from_random_bits(
+                        &party[..comparison.bit_length],
+                        &party[comparison.bit_length..],
+                        party_triples,
+                    )
+                    .unwrap()
+            })
+            .collect();
+
+        let (a_shares, _) = vss.split(&Scalar::from(5u64));
+        let (b_shares, _) = vss.split(&Scalar::from(9u64));
+        let mut parties: Vec<ComparisonProtocol> = a_shares
+            .iter()
+            .zip(&b_shares)
+            .zip(masks)
+            .map(|((&ai, &bi), mask)| comparison.less_than(ai, bi, mask).unwrap())
+            .collect();
+        assert_eq!(run(&mut parties, &vss), Scalar::one());
+    }
+
+    #[test]
+    fn test_mismatched_inputs() {
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: 2,
+            share_amount: 3,
+        };
+        let comparison = SecureComparisonRistretto {
+            threshold: 2,
+            share_amount: 3,
+            bit_length: 4,
+        };
+        let (a_shares, _) = vss.split(&Scalar::from(5u64));
+        let (b_shares, _) = vss.split(&Scalar::from(9u64));
+        let masks = comparison.deal_mask_trusted();
+
+        assert!(comparison
+            .less_than(a_shares[0], b_shares[1], masks[0].clone())
+            .is_none());
+        assert!(comparison
+            .less_than(a_shares[0], b_shares[0], masks[1].clone())
+            .is_none());
+        assert!(comparison
+            .equal(
+                a_shares[0],
+                b_shares[0],
+                (masks[0].clone(), masks[1].clone())
+            )
+            .is_none());
+        let mut short = masks[0].clone();
+        short.bits.pop();
+        assert!(comparison
+            .less_than(a_shares[0], b_shares[0], short)
+            .is_none());
+        let mut foreign = masks[0].clone();
+        foreign.triples[0].c = masks[1].triples[0].c;
+        assert!(comparison
+            .less_than(a_shares[0], b_shares[0], foreign)
+            .is_none());
+        assert!(comparison
+            .less_than(a_shares[0], b_shares[0], masks[0].clone())
+            .is_some());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_bit_length() {
+        let comparison = SecureComparisonRistretto {
+            threshold: 2,
+            share_amount: 3,
+            bit_length: 0,
+        };
+        comparison.deal_mask_trusted();
+    }
+}
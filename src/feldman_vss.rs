@@ -1,3 +1,5 @@
+use crate::lagrange::LagrangeCoefficients;
+use crate::scrape::dual_code_check;
 use curve25519_dalek::ristretto::RistrettoPoint;
 use curve25519_dalek::scalar::Scalar;
 
@@ -54,8 +56,8 @@ impl VerifiableSecretSharingRistretto {
     pub fn recover(&self, shares: &[(usize, Scalar)]) -> Scalar {
         assert!(shares.len() == self.threshold);
 
-        let (xs, ys): (Vec<usize>, Vec<Scalar>) = shares.iter().cloned().unzip();
-        self.lagrange_interpolation(Scalar::zero(), &xs, &ys)
+        let xs: Vec<usize> = shares.iter().map(|share| share.0).collect();
+        LagrangeCoefficients::new(&xs, Scalar::zero()).interpolate(shares)
     }
 
     /// Verify that a specific share is valid (honest, or not corrupted).
@@ -63,13 +65,21 @@ impl VerifiableSecretSharingRistretto {
         let generator = RISTRETTO_BASEPOINT_POINT;
         let (share_index, share_value) = share;
         let share_value_commitment = generator * share_value;
-        let share_index_scalar = from_bigint(&BigInt::from(share_index));
+        share_value_commitment == Self::evaluate_commitments(commitments, share_index)
+    }
+
+    /// Evaluate the committed polynomial "in the exponent" at a share index,
+    /// giving the commitment `generator * share_value` of that share.
+    pub(crate) fn evaluate_commitments(
+        commitments: &[RistrettoPoint],
+        index: usize,
+    ) -> RistrettoPoint {
+        let share_index_scalar = from_bigint(&BigInt::from(index));
         let mut commitments_iter_rev = commitments.iter().rev();
         let commitments_head = commitments_iter_rev.next().unwrap();
-        let share_index_commitment = commitments_iter_rev.fold(*commitments_head, |sum, item| {
+        commitments_iter_rev.fold(*commitments_head, |sum, item| {
             sum * share_index_scalar + *item
-        });
-        share_value_commitment == share_index_commitment
+        })
     }
 
     /// Verify that a set of shares are valid.
@@ -90,10 +100,27 @@ impl VerifiableSecretSharingRistretto {
             }
         }
 
-        return true;
+        true
+    }
+
+    /// Check with the SCRAPE dual-code test that the commitments
+    /// `generator * share_i` of all the shares, in index order, lie on a
+    /// polynomial of degree below the threshold, without evaluating the
+    /// commitments at every index.
+    pub fn verify_degree(&self, share_commitments: &[RistrettoPoint]) -> bool {
+        if share_commitments.len() != self.share_amount {
+            return false;
+        }
+        if self.threshold == self.share_amount {
+            return true;
+        }
+        let dual_polynomial: Vec<Scalar> = (self.threshold..self.share_amount)
+            .map(|_| new_random())
+            .collect();
+        dual_code_check(share_commitments, self.threshold, &dual_polynomial)
     }
 
-    fn generate_commitments(polynomial: &[Scalar]) -> Vec<RistrettoPoint> {
+    pub(crate) fn generate_commitments(polynomial: &[Scalar]) -> Vec<RistrettoPoint> {
         let generator: RistrettoPoint = RISTRETTO_BASEPOINT_POINT;
         (0..polynomial.len())
             .map(|i| generator * polynomial[i])
@@ -121,30 +148,10 @@ impl VerifiableSecretSharingRistretto {
             .rev()
             .fold(Scalar::zero(), |sum, item| scalar_x * sum + *item)
     }
-
-    fn lagrange_interpolation(&self, x: Scalar, xs: &[usize], ys: &[Scalar]) -> Scalar {
-        let scalar_xs: Vec<Scalar> = xs.iter().map(|x| from_bigint(&BigInt::from(*x))).collect();
-        (0..self.threshold).fold(Scalar::zero(), |sum, item| {
-            let numerator: Scalar = (0..self.threshold).fold(Scalar::one(), |product, i| {
-                if i == item {
-                    product
-                } else {
-                    product * (x - scalar_xs[i])
-                }
-            });
-            let denominator: Scalar = (0..self.threshold).fold(Scalar::one(), |product, i| {
-                if i == item {
-                    product
-                } else {
-                    product * (scalar_xs[item] - scalar_xs[i])
-                }
-            });
-            sum + numerator * denominator.invert() * ys[item]
-        })
-    }
 }
 
 #[cfg(test)]
+#[allow(clippy::needless_borrow)]
 mod tests {
     use super::*;
     #[test]
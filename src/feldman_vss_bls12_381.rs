@@ -0,0 +1,80 @@
+use crate::feldman_vss_generic::{evaluate_commitments, recover, split, verify};
+use bls12_381::{G1Projective, Scalar};
+use rand::{thread_rng, Rng};
+
+/// The `VerifiableSecretSharingBls12381` structure, for Feldman VSS over the
+/// scalar field `Fr` of BLS12-381, with commitments in G1 so that
+/// `commitments[0]` is a minimal-pubkey-size BLS public key.
+pub struct VerifiableSecretSharingBls12381 {
+    /// the threshold of shares.
+    pub threshold: usize,
+    /// the total number of shares.
+    pub share_amount: usize,
+}
+
+/// Samples a uniformly random scalar of BLS12-381.
+pub fn new_random_bls12_381() -> Scalar {
+    let mut rand_bytes = [0u8; 64];
+    thread_rng().fill(&mut rand_bytes[..]);
+    Scalar::from_bytes_wide(&rand_bytes)
+}
+
+impl VerifiableSecretSharingBls12381 {
+    /// Split the secret into shares and add commitments (of k size).
+    pub fn split(&self, secret: &Scalar) -> (Vec<(usize, Scalar)>, Vec<G1Projective>) {
+        split(
+            self.threshold,
+            self.share_amount,
+            secret,
+            new_random_bls12_381,
+        )
+    }
+
+    /// Recover the secret with threshold shares.
+    pub fn recover(&self, shares: &[(usize, Scalar)]) -> Scalar {
+        recover(self.threshold, shares)
+    }
+
+    /// Verify that a specific share is valid.
+    pub fn verify(share: (usize, Scalar), commitments: &[G1Projective]) -> bool {
+        verify(share, commitments)
+    }
+
+    /// Verify that a set of shares are valid.
+    pub fn verify_all(shares: &[(usize, Scalar)], commitments: &[G1Projective]) -> bool {
+        shares.iter().all(|&share| verify(share, commitments))
+    }
+
+    /// Evaluate the committed polynomial "in the exponent" at a share index,
+    /// giving the verification share `g1 * share_value` of that share, or the
+    /// identity if there are no commitments.
+    pub fn evaluate_commitments(commitments: &[G1Projective], index: usize) -> G1Projective {
+        evaluate_commitments(commitments, index).unwrap_or_else(G1Projective::identity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integration() {
+        let secret = new_random_bls12_381();
+        let vss = VerifiableSecretSharingBls12381 {
+            threshold: 3,
+            share_amount: 5,
+        };
+        let (shares, commitments) = vss.split(&secret);
+        assert!(VerifiableSecretSharingBls12381::verify_all(
+            &shares,
+            &commitments
+        ));
+        assert_eq!(vss.recover(&shares[1..4]), secret);
+        assert!(!VerifiableSecretSharingBls12381::verify(shares[0], &[]));
+        let tampered = (shares[0].0, shares[0].1 + Scalar::one());
+        assert!(!VerifiableSecretSharingBls12381::verify(
+            tampered,
+            &commitments
+        ));
+    }
+}
@@ -0,0 +1,78 @@
+use crate::feldman_vss_generic::{evaluate_commitments, recover, split, verify};
+use ark_bn254::{Fr, G1Projective};
+use ark_ff::{PrimeField, Zero};
+use rand::{thread_rng, Rng};
+
+/// The `VerifiableSecretSharingBn254` structure, for Feldman VSS over the
+/// scalar field `Fr` of BN254 (alt_bn128), with commitments in G1. The shares
+/// are elements of the field of Groth16 circuits over BN254, so they can be
+/// used as witnesses and public inputs directly.
+pub struct VerifiableSecretSharingBn254 {
+    /// the threshold of shares.
+    pub threshold: usize,
+    /// the total number of shares.
+    pub share_amount: usize,
+}
+
+/// Samples a uniformly random scalar of BN254.
+pub fn new_random_bn254() -> Fr {
+    let mut rand_bytes = [0u8; 64];
+    thread_rng().fill(&mut rand_bytes[..]);
+    Fr::from_le_bytes_mod_order(&rand_bytes)
+}
+
+impl VerifiableSecretSharingBn254 {
+    /// Split the secret into shares and add commitments (of k size).
+    pub fn split(&self, secret: &Fr) -> (Vec<(usize, Fr)>, Vec<G1Projective>) {
+        split(self.threshold, self.share_amount, secret, new_random_bn254)
+    }
+
+    /// Recover the secret with threshold shares.
+    pub fn recover(&self, shares: &[(usize, Fr)]) -> Fr {
+        recover(self.threshold, shares)
+    }
+
+    /// Verify that a specific share is valid.
+    pub fn verify(share: (usize, Fr), commitments: &[G1Projective]) -> bool {
+        verify(share, commitments)
+    }
+
+    /// Verify that a set of shares are valid.
+    pub fn verify_all(shares: &[(usize, Fr)], commitments: &[G1Projective]) -> bool {
+        shares.iter().all(|&share| verify(share, commitments))
+    }
+
+    /// Evaluate the committed polynomial "in the exponent" at a share index,
+    /// giving the verification share `g1 * share_value` of that share, or the
+    /// identity if there are no commitments.
+    pub fn evaluate_commitments(commitments: &[G1Projective], index: usize) -> G1Projective {
+        evaluate_commitments(commitments, index).unwrap_or_else(G1Projective::zero)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ec::Group;
+
+    #[test]
+    fn test_integration() {
+        let secret = new_random_bn254();
+        let vss = VerifiableSecretSharingBn254 {
+            threshold: 3,
+            share_amount: 5,
+        };
+        let (shares, commitments) = vss.split(&secret);
+        assert!(VerifiableSecretSharingBn254::verify_all(
+            &shares,
+            &commitments
+        ));
+        assert_eq!(commitments[0], G1Projective::generator() * secret);
+        assert_eq!(vss.recover(&shares[2..]), secret);
+        let tampered = (shares[4].0, shares[4].1 + Fr::from(1u64));
+        assert!(!VerifiableSecretSharingBn254::verify(
+            tampered,
+            &commitments
+        ));
+    }
+}
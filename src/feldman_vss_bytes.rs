@@ -0,0 +1,176 @@
+use crate::feldman_vss::VerifiableSecretSharingRistretto;
+use crate::lagrange::LagrangeCoefficients;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use std::convert::TryInto;
+
+/// Number of payload bytes packed in each scalar. 31 bytes stay below the
+/// group order, so every chunk maps to a unique canonical scalar.
+const CHUNK_SIZE: usize = 31;
+
+/// Size of the length header prepended to the payload before chunking.
+const LENGTH_SIZE: usize = 8;
+
+/// A share of a byte string: the share identifier and one value per chunk.
+pub type ByteShare = (usize, Vec<Scalar>);
+
+impl VerifiableSecretSharingRistretto {
+    /// Split an arbitrary byte string into shares and commitments.
+    ///
+    /// The byte string is prefixed with its length, chunked into 31-byte
+    /// scalars and each chunk is shared with `split`. Every share carries one
+    /// value per chunk, and there is one commitment vector per chunk.
+    pub fn split_bytes(&self, secret: &[u8]) -> (Vec<ByteShare>, Vec<Vec<RistrettoPoint>>) {
+        let mut payload = (secret.len() as u64).to_le_bytes().to_vec();
+        payload.extend_from_slice(secret);
+
+        let mut shares: Vec<ByteShare> = (1..=self.share_amount).map(|x| (x, Vec::new())).collect();
+        let mut commitments = Vec::new();
+        for chunk in payload.chunks(CHUNK_SIZE) {
+            let (chunk_shares, chunk_commitments) = self.split(&encode_chunk(chunk));
+            for (share, (_, value)) in shares.iter_mut().zip(chunk_shares) {
+                share.1.push(value);
+            }
+            commitments.push(chunk_commitments);
+        }
+        (shares, commitments)
+    }
+
+    /// Recover a byte string with threshold shares.
+    ///
+    /// Returns `None` if two shares have the same identifier, a share does not
+    /// verify against the commitments, or the recovered chunks are not a
+    /// well-formed encoding of a byte string (non-canonical chunk, inconsistent
+    /// length or non-zero padding).
+    pub fn recover_bytes(
+        &self,
+        shares: &[ByteShare],
+        commitments: &[Vec<RistrettoPoint>],
+    ) -> Option<Vec<u8>> {
+        assert!(shares.len() == self.threshold);
+        let mut xs: Vec<usize> = shares.iter().map(|share| share.0).collect();
+        xs.sort_unstable();
+        xs.dedup();
+        if xs.len() != shares.len() {
+            return None;
+        }
+        if commitments
+            .iter()
+            .any(|chunk_commitments| chunk_commitments.len() != self.threshold)
+            || !shares
+                .iter()
+                .all(|share| Self::verify_bytes(share, commitments))
+        {
+            return None;
+        }
+
+        let coefficients = LagrangeCoefficients::new(&xs, Scalar::zero());
+        let mut payload = Vec::with_capacity(commitments.len() * CHUNK_SIZE);
+        for chunk in 0..commitments.len() {
+            let chunk_shares: Vec<(usize, Scalar)> = shares
+                .iter()
+                .map(|(x, values)| (*x, values[chunk]))
+                .collect();
+            payload.extend_from_slice(&decode_chunk(&coefficients.interpolate(&chunk_shares))?);
+        }
+
+        if payload.len() < LENGTH_SIZE {
+            return None;
+        }
+        let length = u64::from_le_bytes(payload[..LENGTH_SIZE].try_into().unwrap()) as usize;
+        let end = LENGTH_SIZE.checked_add(length)?;
+        let expected_chunks = end.div_ceil(CHUNK_SIZE);
+        if expected_chunks != commitments.len() || payload[end..].iter().any(|&b| b != 0) {
+            return None;
+        }
+        Some(payload[LENGTH_SIZE..end].to_vec())
+    }
+
+    /// Verify that a byte string share is valid for every chunk. Returns
+    /// `false` if the number of chunks differs or a commitment vector is empty.
+    pub fn verify_bytes(share: &ByteShare, commitments: &[Vec<RistrettoPoint>]) -> bool {
+        let (share_index, values) = share;
+        values.len() == commitments.len()
+            && values
+                .iter()
+                .zip(commitments)
+                .all(|(value, chunk_commitments)| {
+                    !chunk_commitments.is_empty()
+                        && Self::verify((*share_index, *value), chunk_commitments)
+                })
+    }
+}
+
+fn encode_chunk(chunk: &[u8]) -> Scalar {
+    let mut bytes = [0u8; 32];
+    bytes[..chunk.len()].copy_from_slice(chunk);
+    Scalar::from_bytes_mod_order(bytes)
+}
+
+fn decode_chunk(scalar: &Scalar) -> Option<[u8; CHUNK_SIZE]> {
+    let bytes = scalar.to_bytes();
+    if bytes[CHUNK_SIZE] != 0 {
+        return None;
+    }
+    Some(bytes[..CHUNK_SIZE].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integration() {
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: 3,
+            share_amount: 5,
+        };
+        for length in [0, 1, 23, 24, 64, 1000] {
+            let secret: Vec<u8> = (0..length).map(|i| (i * 7 + 3) as u8).collect();
+            let (shares, commitments) = vss.split_bytes(&secret);
+            assert_eq!(
+                commitments.len(),
+                (LENGTH_SIZE + length).div_ceil(CHUNK_SIZE)
+            );
+            let recovered = vss.recover_bytes(&shares[1..4], &commitments);
+            assert_eq!(recovered, Some(secret));
+        }
+    }
+
+    #[test]
+    fn test_rejects_tampered_share() {
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: 2,
+            share_amount: 3,
+        };
+        let (mut shares, commitments) = vss.split_bytes(b"correct horse battery staple");
+        shares[0].1[0] += Scalar::one();
+        assert!(!VerifiableSecretSharingRistretto::verify_bytes(
+            &shares[0],
+            &commitments
+        ));
+        assert_eq!(vss.recover_bytes(&shares[0..2], &commitments), None);
+        shares[1].1.pop();
+        assert_eq!(vss.recover_bytes(&shares[1..3], &commitments), None);
+
+        let mut empty = commitments.clone();
+        empty[0].clear();
+        assert!(!VerifiableSecretSharingRistretto::verify_bytes(
+            &shares[2], &empty
+        ));
+        empty[0] = commitments[0][..1].to_vec();
+        let (valid, _) = vss.split_bytes(b"x");
+        assert_eq!(vss.recover_bytes(&valid[..2], &empty[..1]), None);
+    }
+
+    #[test]
+    fn test_rejects_duplicate_shares() {
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: 2,
+            share_amount: 3,
+        };
+        let (shares, commitments) = vss.split_bytes(b"correct horse battery staple");
+        let duplicated = [shares[1].clone(), shares[1].clone()];
+        assert_eq!(vss.recover_bytes(&duplicated, &commitments), None);
+    }
+}
@@ -0,0 +1,121 @@
+use crate::feldman_vss::new_random;
+use crate::feldman_vss_generic::{evaluate_commitments, recover, split, verify};
+use curve25519_dalek::edwards::EdwardsPoint;
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::Identity;
+use sha2::{Digest, Sha512};
+use std::convert::TryInto;
+
+/// The `VerifiableSecretSharingEd25519` structure, for Feldman VSS over the
+/// Edwards form of Curve25519 with the Ed25519 base point, so that
+/// `commitments[0].compress()` is the Ed25519 public key of the shared secret.
+///
+/// Shares use the same evaluation points as
+/// `VerifiableSecretSharingRistretto`. The Edwards group has cofactor 8, so
+/// `verify` rejects commitments with a small-order component, which a dealer
+/// could otherwise use to make the commitments disagree with the shares.
+pub struct VerifiableSecretSharingEd25519 {
+    /// the threshold of shares.
+    pub threshold: usize,
+    /// the total number of shares.
+    pub share_amount: usize,
+}
+
+/// Expand an Ed25519 seed (the 32-byte secret key of RFC 8032) into the
+/// clamped secret scalar, reduced modulo the group order, and the 32-byte
+/// nonce prefix.
+///
+/// Sharing the scalar gives the same public key as the seed. The prefix only
+/// derives deterministic nonces for a single signer and must not be shared;
+/// threshold signers have to generate their nonces jointly instead.
+pub fn expand_ed25519_seed(seed: &[u8; 32]) -> (Scalar, [u8; 32]) {
+    let hash = Sha512::digest(seed);
+    let mut lower: [u8; 32] = hash[..32].try_into().unwrap();
+    lower[0] &= 248;
+    lower[31] &= 127;
+    lower[31] |= 64;
+    let prefix: [u8; 32] = hash[32..].try_into().unwrap();
+    (Scalar::from_bytes_mod_order(lower), prefix)
+}
+
+impl VerifiableSecretSharingEd25519 {
+    /// Split the secret into shares and add commitments (of k size).
+    pub fn split(&self, secret: &Scalar) -> (Vec<(usize, Scalar)>, Vec<EdwardsPoint>) {
+        split(self.threshold, self.share_amount, secret, new_random)
+    }
+
+    /// Recover the secret with threshold shares.
+    pub fn recover(&self, shares: &[(usize, Scalar)]) -> Scalar {
+        recover(self.threshold, shares)
+    }
+
+    /// Verify that a specific share is valid, and that the commitments lie in
+    /// the prime-order subgroup.
+    pub fn verify(share: (usize, Scalar), commitments: &[EdwardsPoint]) -> bool {
+        commitments.iter().all(EdwardsPoint::is_torsion_free) && verify(share, commitments)
+    }
+
+    /// Verify that a set of shares are valid.
+    pub fn verify_all(shares: &[(usize, Scalar)], commitments: &[EdwardsPoint]) -> bool {
+        shares.iter().all(|&share| Self::verify(share, commitments))
+    }
+
+    /// Evaluate the committed polynomial "in the exponent" at a share index,
+    /// giving the verification share `basepoint * share_value` of that share,
+    /// or the identity if there are no commitments.
+    pub fn evaluate_commitments(commitments: &[EdwardsPoint], index: usize) -> EdwardsPoint {
+        evaluate_commitments(commitments, index).unwrap_or_else(EdwardsPoint::identity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use curve25519_dalek::constants::EIGHT_TORSION;
+
+    #[test]
+    fn test_integration() {
+        let secret = new_random();
+        let vss = VerifiableSecretSharingEd25519 {
+            threshold: 3,
+            share_amount: 5,
+        };
+        let (shares, commitments) = vss.split(&secret);
+        assert!(VerifiableSecretSharingEd25519::verify_all(
+            &shares,
+            &commitments
+        ));
+        assert_eq!(vss.recover(&shares[2..]), secret);
+
+        // A small-order component in the commitments is rejected.
+        let mut tainted = commitments.clone();
+        tainted[1] += EIGHT_TORSION[1];
+        assert!(!VerifiableSecretSharingEd25519::verify(shares[0], &tainted));
+        assert!(!VerifiableSecretSharingEd25519::verify(shares[0], &[]));
+        let tampered = (shares[0].0, shares[0].1 + Scalar::one());
+        assert!(!VerifiableSecretSharingEd25519::verify(
+            tampered,
+            &commitments
+        ));
+    }
+
+    #[test]
+    fn test_ed25519_seed() {
+        let seed = [7u8; 32];
+        let (secret, _) = expand_ed25519_seed(&seed);
+        let vss = VerifiableSecretSharingEd25519 {
+            threshold: 2,
+            share_amount: 3,
+        };
+        let (shares, commitments) = vss.split(&secret);
+        assert!(VerifiableSecretSharingEd25519::verify_all(
+            &shares,
+            &commitments
+        ));
+
+        let secret_key = ed25519_dalek::SecretKey::from_bytes(&seed).unwrap();
+        let public_key = ed25519_dalek::PublicKey::from(&secret_key);
+        assert_eq!(commitments[0].compress().to_bytes(), public_key.to_bytes());
+        assert_eq!(vss.recover(&shares[1..]), secret);
+    }
+}
@@ -0,0 +1,68 @@
+use crate::field::FieldElement;
+use crate::group::GroupElement;
+use crate::lagrange::LagrangeCoefficients;
+
+/// Split the secret into shares and commitments, with the random
+/// coefficients drawn from `random`.
+pub(crate) fn split<F, G>(
+    threshold: usize,
+    share_amount: usize,
+    secret: &F,
+    random: impl Fn() -> F,
+) -> (Vec<(usize, F)>, Vec<G>)
+where
+    F: FieldElement,
+    G: GroupElement<F>,
+{
+    assert!(threshold <= share_amount);
+
+    let mut polynomial = vec![*secret];
+    polynomial.extend((1..threshold).map(|_| random()));
+    let shares = (1..=share_amount)
+        .map(|x| {
+            let x_scalar = F::from_index(x);
+            let value = polynomial
+                .iter()
+                .rev()
+                .fold(F::zero(), |sum, &item| x_scalar * sum + item);
+            (x, value)
+        })
+        .collect();
+    let commitments = polynomial
+        .iter()
+        .map(|&coefficient| G::generator() * coefficient)
+        .collect();
+    (shares, commitments)
+}
+
+/// Recover the secret with threshold shares.
+pub(crate) fn recover<F: FieldElement>(threshold: usize, shares: &[(usize, F)]) -> F {
+    assert!(shares.len() == threshold);
+
+    let xs: Vec<usize> = shares.iter().map(|share| share.0).collect();
+    LagrangeCoefficients::new(&xs, F::zero()).interpolate(shares)
+}
+
+/// Verify a share against the commitments. Returns false if there are no
+/// commitments.
+pub(crate) fn verify<F, G>(share: (usize, F), commitments: &[G]) -> bool
+where
+    F: FieldElement,
+    G: GroupElement<F>,
+{
+    let (share_index, share_value) = share;
+    evaluate_commitments(commitments, share_index) == Some(G::generator() * share_value)
+}
+
+/// Evaluate the committed polynomial "in the exponent" at a share index.
+/// Returns `None` if there are no commitments.
+pub(crate) fn evaluate_commitments<F, G>(commitments: &[G], index: usize) -> Option<G>
+where
+    F: FieldElement,
+    G: GroupElement<F>,
+{
+    let x = F::from_index(index);
+    let mut iter_rev = commitments.iter().rev();
+    let head = *iter_rev.next()?;
+    Some(iter_rev.fold(head, |sum, &item| sum * x + item))
+}
@@ -0,0 +1,109 @@
+use crate::feldman_vss_generic::{evaluate_commitments, recover, split, verify};
+use crate::scrape::dual_code_check;
+use p256::elliptic_curve::PrimeField;
+use p256::{FieldBytes, ProjectivePoint, Scalar};
+use rand::{thread_rng, Rng};
+
+/// The `VerifiableSecretSharingP256` structure, for Feldman VSS over NIST
+/// P-256 (secp256r1), so that `commitments[0]` is the P-256 public key of the
+/// shared secret.
+pub struct VerifiableSecretSharingP256 {
+    /// the threshold of shares.
+    pub threshold: usize,
+    /// the total number of shares.
+    pub share_amount: usize,
+}
+
+/// Samples a uniformly random scalar of P-256.
+pub fn new_random_p256() -> Scalar {
+    loop {
+        let mut rand_bytes = FieldBytes::default();
+        thread_rng().fill(&mut rand_bytes[..]);
+        if let Some(scalar) = Option::from(Scalar::from_repr(rand_bytes)) {
+            return scalar;
+        }
+    }
+}
+
+impl VerifiableSecretSharingP256 {
+    /// Split the secret into shares and add commitments (of k size).
+    pub fn split(&self, secret: &Scalar) -> (Vec<(usize, Scalar)>, Vec<ProjectivePoint>) {
+        split(self.threshold, self.share_amount, secret, new_random_p256)
+    }
+
+    /// Recover the secret with threshold shares.
+    pub fn recover(&self, shares: &[(usize, Scalar)]) -> Scalar {
+        recover(self.threshold, shares)
+    }
+
+    /// Verify that a specific share is valid.
+    pub fn verify(share: (usize, Scalar), commitments: &[ProjectivePoint]) -> bool {
+        verify(share, commitments)
+    }
+
+    /// Verify that a set of shares are valid.
+    pub fn verify_all(shares: &[(usize, Scalar)], commitments: &[ProjectivePoint]) -> bool {
+        shares.iter().all(|&share| verify(share, commitments))
+    }
+
+    /// Check with the SCRAPE dual-code test that the commitments
+    /// `generator * share_i` of all the shares, in index order, lie on a
+    /// polynomial of degree below the threshold.
+    pub fn verify_degree(&self, share_commitments: &[ProjectivePoint]) -> bool {
+        if share_commitments.len() != self.share_amount {
+            return false;
+        }
+        if self.threshold == self.share_amount {
+            return true;
+        }
+        let dual_polynomial: Vec<Scalar> = (self.threshold..self.share_amount)
+            .map(|_| new_random_p256())
+            .collect();
+        dual_code_check(share_commitments, self.threshold, &dual_polynomial)
+    }
+
+    /// Evaluate the committed polynomial "in the exponent" at a share index,
+    /// giving the verification share `generator * share_value` of that share,
+    /// or the identity if there are no commitments.
+    pub fn evaluate_commitments(commitments: &[ProjectivePoint], index: usize) -> ProjectivePoint {
+        evaluate_commitments(commitments, index).unwrap_or(ProjectivePoint::IDENTITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p256::elliptic_curve::sec1::ToEncodedPoint;
+
+    #[test]
+    fn test_integration() {
+        let secret = new_random_p256();
+        let vss = VerifiableSecretSharingP256 {
+            threshold: 3,
+            share_amount: 5,
+        };
+        let (shares, commitments) = vss.split(&secret);
+        assert!(VerifiableSecretSharingP256::verify_all(
+            &shares,
+            &commitments
+        ));
+        assert_eq!(vss.recover(&shares[1..4]), secret);
+        let tampered = (shares[0].0, shares[0].1 + Scalar::ONE);
+        assert!(!VerifiableSecretSharingP256::verify(tampered, &commitments));
+        assert!(!VerifiableSecretSharingP256::verify(shares[0], &[]));
+
+        let share_commitments: Vec<ProjectivePoint> = shares
+            .iter()
+            .map(|share| ProjectivePoint::GENERATOR * share.1)
+            .collect();
+        assert!(vss.verify_degree(&share_commitments));
+
+        // The public key is the SEC1 encoding of `commitments[0]`.
+        let public_key =
+            p256::PublicKey::from_secret_scalar(&p256::NonZeroScalar::new(secret).unwrap());
+        assert_eq!(
+            public_key.to_encoded_point(true),
+            commitments[0].to_affine().to_encoded_point(true)
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use crate::feldman_vss_generic::{evaluate_commitments, recover, split, verify};
+use ff::FromUniformBytes;
+use pasta_curves::group::Group;
+use pasta_curves::{pallas, vesta};
+use rand::{thread_rng, Rng};
+
+/// The `VerifiableSecretSharingPallas` structure, for Feldman VSS over the
+/// scalar field of Pallas, with commitments in Pallas. The shares are
+/// elements of the base field of Vesta, so they can be witnessed natively in
+/// a Halo2 circuit over Vesta.
+pub struct VerifiableSecretSharingPallas {
+    /// the threshold of shares.
+    pub threshold: usize,
+    /// the total number of shares.
+    pub share_amount: usize,
+}
+
+/// The `VerifiableSecretSharingVesta` structure, for Feldman VSS over the
+/// scalar field of Vesta, with commitments in Vesta. The shares are elements
+/// of the base field of Pallas, so they can be witnessed natively in a Halo2
+/// circuit over Pallas.
+pub struct VerifiableSecretSharingVesta {
+    /// the threshold of shares.
+    pub threshold: usize,
+    /// the total number of shares.
+    pub share_amount: usize,
+}
+
+/// Samples a uniformly random scalar of a Pasta curve.
+pub fn new_random_pasta<F: FromUniformBytes<64>>() -> F {
+    let mut rand_bytes = [0u8; 64];
+    thread_rng().fill(&mut rand_bytes[..]);
+    F::from_uniform_bytes(&rand_bytes)
+}
+
+impl VerifiableSecretSharingPallas {
+    /// Split the secret into shares and add commitments (of k size).
+    pub fn split(
+        &self,
+        secret: &pallas::Scalar,
+    ) -> (Vec<(usize, pallas::Scalar)>, Vec<pallas::Point>) {
+        split(self.threshold, self.share_amount, secret, new_random_pasta)
+    }
+
+    /// Recover the secret with threshold shares.
+    pub fn recover(&self, shares: &[(usize, pallas::Scalar)]) -> pallas::Scalar {
+        recover(self.threshold, shares)
+    }
+
+    /// Verify that a specific share is valid.
+    pub fn verify(share: (usize, pallas::Scalar), commitments: &[pallas::Point]) -> bool {
+        verify(share, commitments)
+    }
+
+    /// Verify that a set of shares are valid.
+    pub fn verify_all(shares: &[(usize, pallas::Scalar)], commitments: &[pallas::Point]) -> bool {
+        shares.iter().all(|&share| verify(share, commitments))
+    }
+
+    /// Evaluate the committed polynomial "in the exponent" at a share index,
+    /// giving the verification share `generator * share_value` of that share,
+    /// or the identity if there are no commitments.
+    pub fn evaluate_commitments(commitments: &[pallas::Point], index: usize) -> pallas::Point {
+        evaluate_commitments(commitments, index).unwrap_or_else(Group::identity)
+    }
+}
+
+impl VerifiableSecretSharingVesta {
+    /// Split the secret into shares and add commitments (of k size).
+    pub fn split(
+        &self,
+        secret: &vesta::Scalar,
+    ) -> (Vec<(usize, vesta::Scalar)>, Vec<vesta::Point>) {
+        split(self.threshold, self.share_amount, secret, new_random_pasta)
+    }
+
+    /// Recover the secret with threshold shares.
+    pub fn recover(&self, shares: &[(usize, vesta::Scalar)]) -> vesta::Scalar {
+        recover(self.threshold, shares)
+    }
+
+    /// Verify that a specific share is valid.
+    pub fn verify(share: (usize, vesta::Scalar), commitments: &[vesta::Point]) -> bool {
+        verify(share, commitments)
+    }
+
+    /// Verify that a set of shares are valid.
+    pub fn verify_all(shares: &[(usize, vesta::Scalar)], commitments: &[vesta::Point]) -> bool {
+        shares.iter().all(|&share| verify(share, commitments))
+    }
+
+    /// Evaluate the committed polynomial "in the exponent" at a share index,
+    /// giving the verification share `generator * share_value` of that share,
+    /// or the identity if there are no commitments.
+    pub fn evaluate_commitments(commitments: &[vesta::Point], index: usize) -> vesta::Point {
+        evaluate_commitments(commitments, index).unwrap_or_else(Group::identity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ff::Field;
+    use pasta_curves::group::Group;
+
+    #[test]
+    fn test_pallas() {
+        let secret: pallas::Scalar = new_random_pasta();
+        let vss = VerifiableSecretSharingPallas {
+            threshold: 3,
+            share_amount: 5,
+        };
+        let (shares, commitments) = vss.split(&secret);
+        assert!(VerifiableSecretSharingPallas::verify_all(
+            &shares,
+            &commitments
+        ));
+        assert_eq!(commitments[0], pallas::Point::generator() * secret);
+        assert_eq!(vss.recover(&shares[..3]), secret);
+        let tampered = (shares[0].0, shares[0].1 + pallas::Scalar::ONE);
+        assert!(!VerifiableSecretSharingPallas::verify(
+            tampered,
+            &commitments
+        ));
+    }
+
+    #[test]
+    fn test_vesta() {
+        let secret: vesta::Scalar = new_random_pasta();
+        let vss = VerifiableSecretSharingVesta {
+            threshold: 2,
+            share_amount: 4,
+        };
+        let (shares, commitments) = vss.split(&secret);
+        assert!(VerifiableSecretSharingVesta::verify_all(
+            &shares,
+            &commitments
+        ));
+        assert_eq!(vss.recover(&[shares[3], shares[1]]), secret);
+    }
+}
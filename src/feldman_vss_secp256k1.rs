@@ -1,3 +1,5 @@
+use crate::lagrange::LagrangeCoefficients;
+use crate::scrape::dual_code_check;
 use crate::secp256k1_helper::{Secp256k1Point, Secp256k1Scalar};
 use num_bigint_dig::BigInt;
 
@@ -27,8 +29,8 @@ impl VerifiableSecretSharing {
     pub fn recover(&self, shares: &[(usize, Secp256k1Scalar)]) -> Secp256k1Scalar {
         assert!(shares.len() == self.threshold);
 
-        let (xs, ys): (Vec<usize>, Vec<Secp256k1Scalar>) = shares.iter().cloned().unzip();
-        self.lagrange_interpolation(Secp256k1Scalar::zero(), &xs, &ys)
+        let xs: Vec<usize> = shares.iter().map(|share| share.0).collect();
+        LagrangeCoefficients::new(&xs, Secp256k1Scalar::zero()).interpolate(shares)
     }
 
     /// Verify a specific share distributed by the dealer is valid.
@@ -67,7 +69,24 @@ impl VerifiableSecretSharing {
             }
         }
 
-        return true;
+        true
+    }
+
+    /// Check with the SCRAPE dual-code test that the commitments
+    /// `generator * share_i` of all the shares, in index order, lie on a
+    /// polynomial of degree below the threshold, without evaluating the
+    /// commitments at every index.
+    pub fn verify_degree(&self, share_commitments: &[Secp256k1Point]) -> bool {
+        if share_commitments.len() != self.share_amount {
+            return false;
+        }
+        if self.threshold == self.share_amount {
+            return true;
+        }
+        let dual_polynomial: Vec<Secp256k1Scalar> = (self.threshold..self.share_amount)
+            .map(|_| Secp256k1Scalar::new_random())
+            .collect();
+        dual_code_check(share_commitments, self.threshold, &dual_polynomial)
     }
 
     fn generate_commitments(polynomial: &[Secp256k1Scalar]) -> Vec<Secp256k1Point> {
@@ -100,40 +119,10 @@ impl VerifiableSecretSharing {
                 (scalar_x * sum + *item).mod_scalar()
             })
     }
-
-    fn lagrange_interpolation(
-        &self,
-        x: Secp256k1Scalar,
-        xs: &[usize],
-        ys: &[Secp256k1Scalar],
-    ) -> Secp256k1Scalar {
-        let scalar_xs: Vec<Secp256k1Scalar> = xs
-            .iter()
-            .map(|x| Secp256k1Scalar::from_bigint(&BigInt::from(*x)))
-            .collect();
-        (0..self.threshold).fold(Secp256k1Scalar::zero(), |sum, item| {
-            let numerator: Secp256k1Scalar =
-                (0..self.threshold).fold(Secp256k1Scalar::one(), |product, i| {
-                    if i == item {
-                        product
-                    } else {
-                        (product * (x - scalar_xs[i])).mod_scalar()
-                    }
-                });
-            let denominator: Secp256k1Scalar =
-                (0..self.threshold).fold(Secp256k1Scalar::one(), |product, i| {
-                    if i == item {
-                        product
-                    } else {
-                        product * (scalar_xs[item] - scalar_xs[i]).mod_scalar()
-                    }
-                });
-            (sum + numerator * denominator.inv() * ys[item]).mod_scalar()
-        })
-    }
 }
 
 #[cfg(test)]
+#[allow(clippy::needless_borrow)]
 mod tests {
     use super::*;
     #[test]
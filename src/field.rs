@@ -0,0 +1,204 @@
+use crate::feldman_vss::from_bigint;
+use crate::secp256k1_helper::Secp256k1Scalar;
+use ark_bn254::Fr as Bn254Scalar;
+use bls12_381::Scalar as BlsScalar;
+use curve25519_dalek::scalar::Scalar;
+use ff::Field;
+use num_bigint_dig::BigInt;
+use p256::Scalar as P256Scalar;
+use pasta_curves::{Fp as PastaFp, Fq as PastaFq};
+use std::ops::{Add, Mul, Sub};
+
+/// Arithmetic over the scalar field of a prime-order group.
+///
+/// Implemented for the Ristretto, secp256k1, BLS12-381, P-256, Pasta and BN254
+/// scalars, so that helpers which only need field operations can be shared
+/// between the curves.
+pub trait FieldElement:
+    Copy + PartialEq + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    /// Returns the additive identity.
+    fn zero() -> Self;
+
+    /// Returns the multiplicative identity.
+    fn one() -> Self;
+
+    /// Maps a share identifier to the evaluation point the VSS of this field
+    /// uses for it.
+    fn from_index(index: usize) -> Self;
+
+    /// Maps an integer into the field.
+    ///
+    /// The default adds up `one()` by double-and-add; fields with a native
+    /// conversion override it.
+    fn from_u64(n: u64) -> Self {
+        (0..64).rev().fold(Self::zero(), |sum, bit| {
+            let double = sum + sum;
+            if (n >> bit) & 1 == 1 {
+                double + Self::one()
+            } else {
+                double
+            }
+        })
+    }
+
+    /// Returns the multiplicative inverse. Panics on zero.
+    fn invert(&self) -> Self;
+}
+
+impl FieldElement for Scalar {
+    fn zero() -> Self {
+        Scalar::zero()
+    }
+
+    fn one() -> Self {
+        Scalar::one()
+    }
+
+    fn from_index(index: usize) -> Self {
+        // Same embedding as `VerifiableSecretSharingRistretto`, so that shares
+        // can be interpolated at points other than zero.
+        from_bigint(&BigInt::from(index))
+    }
+
+    fn from_u64(n: u64) -> Self {
+        Scalar::from(n)
+    }
+
+    fn invert(&self) -> Self {
+        assert!(*self != Scalar::zero());
+        Scalar::invert(self)
+    }
+}
+
+impl FieldElement for Secp256k1Scalar {
+    fn zero() -> Self {
+        Secp256k1Scalar::zero()
+    }
+
+    fn one() -> Self {
+        Secp256k1Scalar::one()
+    }
+
+    fn from_index(index: usize) -> Self {
+        Secp256k1Scalar::from_bigint(&BigInt::from(index))
+    }
+
+    fn from_u64(n: u64) -> Self {
+        Secp256k1Scalar::from_bigint(&BigInt::from(n))
+    }
+
+    fn invert(&self) -> Self {
+        self.inv()
+    }
+}
+
+impl FieldElement for BlsScalar {
+    fn zero() -> Self {
+        BlsScalar::zero()
+    }
+
+    fn one() -> Self {
+        BlsScalar::one()
+    }
+
+    fn from_index(index: usize) -> Self {
+        BlsScalar::from(index as u64)
+    }
+
+    fn from_u64(n: u64) -> Self {
+        BlsScalar::from(n)
+    }
+
+    fn invert(&self) -> Self {
+        BlsScalar::invert(self).unwrap()
+    }
+}
+
+impl FieldElement for P256Scalar {
+    fn zero() -> Self {
+        P256Scalar::ZERO
+    }
+
+    fn one() -> Self {
+        P256Scalar::ONE
+    }
+
+    fn from_index(index: usize) -> Self {
+        P256Scalar::from(index as u64)
+    }
+
+    fn from_u64(n: u64) -> Self {
+        P256Scalar::from(n)
+    }
+
+    fn invert(&self) -> Self {
+        Field::invert(self).unwrap()
+    }
+}
+
+impl FieldElement for PastaFp {
+    fn zero() -> Self {
+        PastaFp::ZERO
+    }
+
+    fn one() -> Self {
+        PastaFp::ONE
+    }
+
+    fn from_index(index: usize) -> Self {
+        PastaFp::from(index as u64)
+    }
+
+    fn from_u64(n: u64) -> Self {
+        PastaFp::from(n)
+    }
+
+    fn invert(&self) -> Self {
+        Field::invert(self).unwrap()
+    }
+}
+
+impl FieldElement for PastaFq {
+    fn zero() -> Self {
+        PastaFq::ZERO
+    }
+
+    fn one() -> Self {
+        PastaFq::ONE
+    }
+
+    fn from_index(index: usize) -> Self {
+        PastaFq::from(index as u64)
+    }
+
+    fn from_u64(n: u64) -> Self {
+        PastaFq::from(n)
+    }
+
+    fn invert(&self) -> Self {
+        Field::invert(self).unwrap()
+    }
+}
+
+impl FieldElement for Bn254Scalar {
+    fn zero() -> Self {
+        Bn254Scalar::from(0u64)
+    }
+
+    fn one() -> Self {
+        Bn254Scalar::from(1u64)
+    }
+
+    fn from_index(index: usize) -> Self {
+        Bn254Scalar::from(index as u64)
+    }
+
+    fn from_u64(n: u64) -> Self {
+        Bn254Scalar::from(n)
+    }
+
+    fn invert(&self) -> Self {
+        ark_ff::Field::inverse(self).unwrap()
+    }
+}
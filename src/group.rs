@@ -0,0 +1,115 @@
+use crate::field::FieldElement;
+use crate::secp256k1_helper::{Secp256k1Point, Secp256k1Scalar};
+use ark_bn254::{Fr as Bn254Scalar, G1Projective as Bn254G1};
+use bls12_381::{G1Projective, G2Projective, Scalar as BlsScalar};
+use curve25519_dalek::constants::{ED25519_BASEPOINT_POINT, RISTRETTO_BASEPOINT_POINT};
+use curve25519_dalek::edwards::EdwardsPoint;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use p256::{ProjectivePoint as P256Point, Scalar as P256Scalar};
+use pasta_curves::{pallas, vesta};
+use std::ops::{Add, Mul};
+
+/// A prime-order group with scalar field `F`, in which the commitments live.
+///
+/// Implemented for Ristretto, Edwards25519, secp256k1, P-256, Pallas, Vesta,
+/// BN254 G1 and the BLS12-381 groups, so that Feldman-style checks can be
+/// shared between the curves.
+pub trait GroupElement<F: FieldElement>:
+    Copy + PartialEq + Add<Output = Self> + Mul<F, Output = Self>
+{
+    /// Returns the generator the commitments are computed with.
+    fn generator() -> Self;
+
+    /// Returns `self + other`, or `None` if the sum is the identity and the
+    /// group can not represent it, as for secp256k1.
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(*self + *other)
+    }
+
+    /// Returns `self * scalar`, or `None` if the product is the identity and
+    /// the group can not represent it.
+    fn checked_mul(&self, scalar: &F) -> Option<Self> {
+        Some(*self * *scalar)
+    }
+}
+
+/// Computes `sum_i points[i] * scalars[i]`, with `None` standing for the
+/// identity, so that it never panics in groups lacking one.
+pub(crate) fn linear_combination<'a, F, G>(
+    terms: impl IntoIterator<Item = (&'a G, &'a F)>,
+) -> Option<G>
+where
+    F: FieldElement + 'a,
+    G: GroupElement<F> + 'a,
+{
+    terms.into_iter().fold(None, |sum, (point, scalar)| {
+        match (sum, point.checked_mul(scalar)) {
+            (Some(sum), Some(term)) => sum.checked_add(&term),
+            (sum, None) => sum,
+            (None, term) => term,
+        }
+    })
+}
+
+impl GroupElement<Scalar> for RistrettoPoint {
+    fn generator() -> Self {
+        RISTRETTO_BASEPOINT_POINT
+    }
+}
+
+impl GroupElement<Scalar> for EdwardsPoint {
+    fn generator() -> Self {
+        ED25519_BASEPOINT_POINT
+    }
+}
+
+impl GroupElement<Secp256k1Scalar> for Secp256k1Point {
+    fn generator() -> Self {
+        Secp256k1Point::generator()
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Secp256k1Point::checked_add(self, other)
+    }
+
+    fn checked_mul(&self, scalar: &Secp256k1Scalar) -> Option<Self> {
+        Secp256k1Point::checked_mul(self, scalar)
+    }
+}
+
+impl GroupElement<BlsScalar> for G1Projective {
+    fn generator() -> Self {
+        G1Projective::generator()
+    }
+}
+
+impl GroupElement<BlsScalar> for G2Projective {
+    fn generator() -> Self {
+        G2Projective::generator()
+    }
+}
+
+impl GroupElement<P256Scalar> for P256Point {
+    fn generator() -> Self {
+        P256Point::GENERATOR
+    }
+}
+
+impl GroupElement<pallas::Scalar> for pallas::Point {
+    fn generator() -> Self {
+        pasta_curves::group::Group::generator()
+    }
+}
+
+impl GroupElement<vesta::Scalar> for vesta::Point {
+    fn generator() -> Self {
+        pasta_curves::group::Group::generator()
+    }
+}
+
+impl GroupElement<Bn254Scalar> for Bn254G1 {
+    fn generator() -> Self {
+        ark_ec::Group::generator()
+    }
+}
@@ -0,0 +1,372 @@
+use crate::feldman_vss::new_random;
+use crate::field::FieldElement;
+use crate::group::GroupElement;
+use crate::secp256k1_helper::{Secp256k1Point, Secp256k1Scalar};
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+
+/// A share of a hierarchical sharing: the value of the `derivative`-th
+/// derivative of the polynomial at the identifier's point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HierarchicalShare<F> {
+    /// the share identifier.
+    pub identifier: usize,
+    /// the level of the holder, 0 being the most senior.
+    pub level: usize,
+    /// the order of the derivative the share is a value of.
+    pub derivative: usize,
+    /// the share value.
+    pub value: F,
+}
+
+/// The `HierarchicalSecretSharingRistretto` structure, for Tassa's
+/// hierarchical threshold secret sharing over Ristretto.
+///
+/// A set of shares is authorized when, for every level `j`, it holds at least
+/// `thresholds[j]` shares from levels `0..=j`. Level 0 receives values of the
+/// polynomial, and lower levels values of its `thresholds[j - 1]`-th
+/// derivative, so they can not stand in for more senior holders.
+pub struct HierarchicalSecretSharingRistretto {
+    /// the cumulative threshold of each level, strictly increasing; the last
+    /// one is the total number of shares needed.
+    pub thresholds: Vec<usize>,
+    /// the number of shares of each level.
+    pub share_amounts: Vec<usize>,
+}
+
+/// The `HierarchicalSecretSharing` structure, for Tassa's hierarchical
+/// threshold secret sharing over secp256k1. See
+/// `HierarchicalSecretSharingRistretto` for the access structure.
+pub struct HierarchicalSecretSharing {
+    /// the cumulative threshold of each level, strictly increasing; the last
+    /// one is the total number of shares needed.
+    pub thresholds: Vec<usize>,
+    /// the number of shares of each level.
+    pub share_amounts: Vec<usize>,
+}
+
+impl HierarchicalSecretSharingRistretto {
+    /// Split the secret into shares and add commitments (of size the last
+    /// threshold).
+    pub fn split(&self, secret: &Scalar) -> (Vec<HierarchicalShare<Scalar>>, Vec<RistrettoPoint>) {
+        check_parameters(&self.thresholds, &self.share_amounts);
+        let mut polynomial = vec![*secret];
+        polynomial.extend((1..*self.thresholds.last().unwrap()).map(|_| new_random()));
+        deal(&self.thresholds, &self.share_amounts, &polynomial)
+    }
+
+    /// Recover the secret with an authorized set of shares, by Birkhoff
+    /// interpolation. Returns `None` if the set is not authorized, or if the
+    /// identifiers do not grow with the level as `split` hands them out.
+    ///
+    /// Birkhoff interpolation can fail on authorized sets: with thresholds
+    /// `[1, 3]`, level 0 shares at `a` and `b` and a level 1 share at
+    /// `(a + b) / 2` do not determine the polynomial. Tassa shows that the
+    /// monotone allocation avoids this whenever the field order exceeds about
+    /// `N^(k (k - 1) / 2)`, for `N` the largest identifier and `k` the last
+    /// threshold. Beyond that bound a singular system also gives `None`.
+    pub fn recover(&self, shares: &[HierarchicalShare<Scalar>]) -> Option<Scalar> {
+        birkhoff_recover(&self.thresholds, shares)
+    }
+
+    /// Verify that a specific (derivative) share is valid.
+    pub fn verify(share: &HierarchicalShare<Scalar>, commitments: &[RistrettoPoint]) -> bool {
+        verify_derivative(share, commitments)
+    }
+
+    /// Verify that a set of shares are valid.
+    pub fn verify_all(
+        shares: &[HierarchicalShare<Scalar>],
+        commitments: &[RistrettoPoint],
+    ) -> bool {
+        shares
+            .iter()
+            .all(|share| verify_derivative(share, commitments))
+    }
+}
+
+impl HierarchicalSecretSharing {
+    /// Split the secret into shares and add commitments (of size the last
+    /// threshold).
+    pub fn split(
+        &self,
+        secret: &Secp256k1Scalar,
+    ) -> (Vec<HierarchicalShare<Secp256k1Scalar>>, Vec<Secp256k1Point>) {
+        check_parameters(&self.thresholds, &self.share_amounts);
+        let mut polynomial = vec![*secret];
+        polynomial
+            .extend((1..*self.thresholds.last().unwrap()).map(|_| Secp256k1Scalar::new_random()));
+        deal(&self.thresholds, &self.share_amounts, &polynomial)
+    }
+
+    /// Recover the secret with an authorized set of shares, by Birkhoff
+    /// interpolation. Returns `None` in the same cases as
+    /// `HierarchicalSecretSharingRistretto::recover`.
+    pub fn recover(
+        &self,
+        shares: &[HierarchicalShare<Secp256k1Scalar>],
+    ) -> Option<Secp256k1Scalar> {
+        birkhoff_recover(&self.thresholds, shares)
+    }
+
+    /// Verify that a specific (derivative) share is valid.
+    pub fn verify(
+        share: &HierarchicalShare<Secp256k1Scalar>,
+        commitments: &[Secp256k1Point],
+    ) -> bool {
+        verify_derivative(share, commitments)
+    }
+
+    /// Verify that a set of shares are valid.
+    pub fn verify_all(
+        shares: &[HierarchicalShare<Secp256k1Scalar>],
+        commitments: &[Secp256k1Point],
+    ) -> bool {
+        shares
+            .iter()
+            .all(|share| verify_derivative(share, commitments))
+    }
+}
+
+fn check_parameters(thresholds: &[usize], share_amounts: &[usize]) {
+    assert!(!thresholds.is_empty() && thresholds.len() == share_amounts.len());
+    assert!(thresholds[0] > 0);
+    assert!(thresholds.windows(2).all(|pair| pair[0] < pair[1]));
+    // Every level's threshold must be reachable with the more senior shares.
+    let mut available = 0;
+    for (threshold, amount) in thresholds.iter().zip(share_amounts) {
+        available += amount;
+        assert!(*threshold <= available);
+    }
+}
+
+/// The order of the derivative handed out at `level`.
+fn derivative_of_level(thresholds: &[usize], level: usize) -> usize {
+    if level == 0 {
+        0
+    } else {
+        thresholds[level - 1]
+    }
+}
+
+/// `i! / (i - d)!`, the factor of `X^(i - d)` in the `d`-th derivative of `X^i`.
+fn falling_factorial<F: FieldElement>(i: usize, d: usize) -> F {
+    (i - d + 1..=i).fold(F::one(), |product, j| product * F::from_u64(j as u64))
+}
+
+/// The coefficients, lowest degree first, of the `d`-th derivative.
+fn derivative<F: FieldElement>(polynomial: &[F], d: usize) -> Vec<F> {
+    (d..polynomial.len())
+        .map(|i| falling_factorial::<F>(i, d) * polynomial[i])
+        .collect()
+}
+
+/// Hand out shares in the monotone allocation: identifiers `1..=n` are given
+/// level by level, starting with the most senior one.
+fn deal<F, G>(
+    thresholds: &[usize],
+    share_amounts: &[usize],
+    polynomial: &[F],
+) -> (Vec<HierarchicalShare<F>>, Vec<G>)
+where
+    F: FieldElement,
+    G: GroupElement<F>,
+{
+    let mut shares = Vec::new();
+    let mut identifier = 0;
+    for (level, amount) in share_amounts.iter().enumerate() {
+        let order = derivative_of_level(thresholds, level);
+        let derived = derivative(polynomial, order);
+        for _ in 0..*amount {
+            identifier += 1;
+            let x = F::from_index(identifier);
+            let value = derived
+                .iter()
+                .rev()
+                .fold(F::zero(), |sum, item| x * sum + *item);
+            shares.push(HierarchicalShare {
+                identifier,
+                level,
+                derivative: order,
+                value,
+            });
+        }
+    }
+    let commitments = polynomial
+        .iter()
+        .map(|coefficient| G::generator() * *coefficient)
+        .collect();
+    (shares, commitments)
+}
+
+fn verify_derivative<F, G>(share: &HierarchicalShare<F>, commitments: &[G]) -> bool
+where
+    F: FieldElement,
+    G: GroupElement<F>,
+{
+    let d = share.derivative;
+    if d >= commitments.len() {
+        return false;
+    }
+    let x = F::from_index(share.identifier);
+    let mut power = F::one();
+    let mut terms = commitments
+        .iter()
+        .enumerate()
+        .skip(d)
+        .map(|(i, commitment)| {
+            let term = *commitment * (falling_factorial::<F>(i, d) * power);
+            power = power * x;
+            term
+        });
+    let head = terms.next().unwrap();
+    G::generator() * share.value == terms.fold(head, |sum, term| sum + term)
+}
+
+fn birkhoff_recover<F: FieldElement>(
+    thresholds: &[usize],
+    shares: &[HierarchicalShare<F>],
+) -> Option<F> {
+    let k = *thresholds.last()?;
+    let mut sorted: Vec<&HierarchicalShare<F>> = shares.iter().collect();
+    sorted.sort_by_key(|share| (share.level, share.identifier));
+    // The monotone allocation, for which Tassa's non-singularity bound holds.
+    if sorted
+        .windows(2)
+        .any(|pair| pair[0].identifier >= pair[1].identifier)
+    {
+        return None;
+    }
+    for share in &sorted {
+        if share.level >= thresholds.len()
+            || share.derivative != derivative_of_level(thresholds, share.level)
+        {
+            return None;
+        }
+    }
+    for (level, threshold) in thresholds.iter().enumerate() {
+        if sorted.iter().filter(|share| share.level <= level).count() < *threshold {
+            return None;
+        }
+    }
+
+    // Keeping the k most senior shares preserves the authorization.
+    let chosen = &sorted[..k];
+    let mut matrix: Vec<Vec<F>> = chosen
+        .iter()
+        .map(|share| {
+            let x = F::from_index(share.identifier);
+            let d = share.derivative;
+            let mut row = vec![F::zero(); k + 1];
+            let mut power = F::one();
+            for (i, entry) in row.iter_mut().enumerate().take(k).skip(d) {
+                *entry = falling_factorial::<F>(i, d) * power;
+                power = power * x;
+            }
+            row[k] = share.value;
+            row
+        })
+        .collect();
+    solve(&mut matrix).map(|solution| solution[0])
+}
+
+/// Solve the linear system given as an augmented `k x (k + 1)` matrix by
+/// Gaussian elimination. Returns `None` if the system is singular.
+fn solve<F: FieldElement>(matrix: &mut [Vec<F>]) -> Option<Vec<F>> {
+    let k = matrix.len();
+    for column in 0..k {
+        let pivot = (column..k).find(|&row| matrix[row][column] != F::zero())?;
+        matrix.swap(column, pivot);
+        let inverse = matrix[column][column].invert();
+        for entry in matrix[column].iter_mut() {
+            *entry = *entry * inverse;
+        }
+        let pivot_row = matrix[column].clone();
+        for (row, entries) in matrix.iter_mut().enumerate() {
+            let factor = entries[column];
+            if row != column && factor != F::zero() {
+                for (entry, value) in entries.iter_mut().zip(&pivot_row).skip(column) {
+                    *entry = *entry - factor * *value;
+                }
+            }
+        }
+    }
+    Some(matrix.iter().map(|row| row[k]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integration() {
+        // At least 2 shares, at least 1 of which from the executive tier.
+        let secret = new_random();
+        let hss = HierarchicalSecretSharingRistretto {
+            thresholds: vec![1, 2],
+            share_amounts: vec![2, 4],
+        };
+        let (shares, commitments) = hss.split(&secret);
+        assert!(HierarchicalSecretSharingRistretto::verify_all(
+            &shares,
+            &commitments
+        ));
+        assert_eq!(hss.recover(&[shares[0], shares[4]]), Some(secret));
+        assert_eq!(hss.recover(&[shares[1], shares[0]]), Some(secret));
+        assert_eq!(hss.recover(&[shares[2], shares[5]]), None);
+        assert_eq!(hss.recover(&[shares[2], shares[3], shares[5]]), None);
+
+        let mut tampered = shares[3];
+        tampered.value += Scalar::one();
+        assert!(!HierarchicalSecretSharingRistretto::verify(
+            &tampered,
+            &commitments
+        ));
+    }
+
+    #[test]
+    fn test_integration_secp256k1() {
+        let secret = Secp256k1Scalar::new_random();
+        let hss = HierarchicalSecretSharing {
+            thresholds: vec![2, 3, 5],
+            share_amounts: vec![3, 3, 4],
+        };
+        let (shares, commitments) = hss.split(&secret);
+        assert!(HierarchicalSecretSharing::verify_all(&shares, &commitments));
+        let authorized = [shares[0], shares[2], shares[4], shares[7], shares[9]];
+        assert_eq!(hss.recover(&authorized), Some(secret));
+        let unauthorized = [shares[0], shares[3], shares[4], shares[7], shares[9]];
+        assert_eq!(hss.recover(&unauthorized), None);
+    }
+
+    #[test]
+    fn test_singular_allocation() {
+        // Level 0 shares at 1 and 3 and a level 1 share at 2 are authorized,
+        // but their Birkhoff system is singular, so they are rejected.
+        let hss = HierarchicalSecretSharing {
+            thresholds: vec![1, 3],
+            share_amounts: vec![2, 1],
+        };
+        let polynomial: Vec<Secp256k1Scalar> =
+            (0..3).map(|_| Secp256k1Scalar::new_random()).collect();
+        let share = |identifier: usize, level: usize| {
+            let order = derivative_of_level(&hss.thresholds, level);
+            let x = Secp256k1Scalar::from_index(identifier);
+            let value = derivative(&polynomial, order)
+                .iter()
+                .rev()
+                .fold(Secp256k1Scalar::zero(), |sum, item| x * sum + *item);
+            HierarchicalShare {
+                identifier,
+                level,
+                derivative: order,
+                value,
+            }
+        };
+        assert_eq!(hss.recover(&[share(1, 0), share(3, 0), share(2, 1)]), None);
+        assert_eq!(
+            hss.recover(&[share(1, 0), share(2, 0), share(3, 1)]),
+            Some(polynomial[0])
+        );
+    }
+}
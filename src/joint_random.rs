@@ -0,0 +1,244 @@
+use crate::feldman_vss::{new_random, VerifiableSecretSharingRistretto};
+use crate::share::{Commitments, Share, SubShare};
+use curve25519_dalek::constants::BASEPOINT_ORDER;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use num_bigint_dig::BigUint;
+
+/// A party's share of a jointly generated value, with the public commitments
+/// of the sharing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JointShare {
+    /// the party's share.
+    pub share: Share<Scalar>,
+    /// the commitments of the sharing, the same for every party.
+    pub commitments: Commitments<RistrettoPoint>,
+}
+
+impl JointShare {
+    /// Verify the share against the commitments.
+    pub fn verify(&self) -> bool {
+        self.share.verify(&self.commitments)
+    }
+}
+
+/// The `JointRandomRistretto` structure, for generating a sharing of a random
+/// value that no party knows, without a dealer.
+///
+/// Every party `deal`s a sharing of a random scalar and sends
+/// `sub_shares[j - 1]` privately to party `j`; the sum of the verified
+/// sub-shares is uniform as long as one dealer is honest. A random bit
+/// follows from a random value `r` by squaring it with
+/// `BgwMultiplicationRistretto` or `BeaverMultiplicationRistretto`, opening
+/// `r^2` and calling `random_bit`.
+pub struct JointRandomRistretto {
+    /// the threshold of shares.
+    pub threshold: usize,
+    /// the total number of shares.
+    pub share_amount: usize,
+}
+
+impl JointRandomRistretto {
+    /// Deal a sharing of a fresh random scalar, as party `dealer`.
+    pub fn deal(&self, dealer: usize) -> Vec<SubShare<Scalar, RistrettoPoint>> {
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: self.threshold,
+            share_amount: self.share_amount,
+        };
+        SubShare::from_split(dealer, vss.split(&new_random()))
+    }
+
+    /// Sum the sub-shares received by `recipient` into its share of the
+    /// random value. Returns `None` if there are none, a dealer appears twice
+    /// or a sub-share does not verify, in which case the parties have to agree
+    /// on the qualified dealers and combine again without the faulty ones.
+    pub fn combine(
+        &self,
+        recipient: usize,
+        sub_shares: &[SubShare<Scalar, RistrettoPoint>],
+    ) -> Option<JointShare> {
+        let mut dealers: Vec<usize> = sub_shares.iter().map(|sub| sub.dealer).collect();
+        dealers.sort_unstable();
+        dealers.dedup();
+        if sub_shares.is_empty() || dealers.len() != sub_shares.len() {
+            return None;
+        }
+        let mut value = Share::from((recipient, Scalar::zero()));
+        let mut commitments = Commitments(vec![]);
+        for sub in sub_shares {
+            if !sub.verify(recipient, self.threshold) {
+                return None;
+            }
+            value = value + sub.share;
+            commitments = commitments + sub.commitments.clone();
+        }
+        Some(JointShare {
+            share: value,
+            commitments,
+        })
+    }
+
+    /// Derive a party's share of a uniformly random bit from its share of a
+    /// random value `r` and the opened square `r^2`: with `root` the canonical
+    /// square root of `r^2`, the bit is `(r / root + 1) / 2`. Returns `None`
+    /// if the square is zero, in which case a new `r` is needed, or not a
+    /// square.
+    pub fn random_bit(r: &JointShare, square: &Scalar) -> Option<JointShare> {
+        let root = sqrt(square)?;
+        if root == Scalar::zero() {
+            return None;
+        }
+        let (root_inverse, half) = (root.invert(), Scalar::from(2u64).invert());
+        Some(JointShare {
+            share: (r.share * root_inverse + Scalar::one()) * half,
+            commitments: (r.commitments.clone() * root_inverse).add_constant(Scalar::one())? * half,
+        })
+    }
+}
+
+/// The square root of `a` with the even canonical encoding, by Atkin's method
+/// for the group order `l = 5 mod 8`.
+fn sqrt(a: &Scalar) -> Option<Scalar> {
+    let exponent = (BigUint::from_bytes_le(BASEPOINT_ORDER.as_bytes()) - 5u32) >> 3;
+    let two_a = a + a;
+    let v = pow(&two_a, &exponent.to_bytes_le());
+    let i = two_a * v * v;
+    let root = a * v * (i - Scalar::one());
+    if root * root != *a {
+        return None;
+    }
+    Some(if root.as_bytes()[0] & 1 == 1 {
+        -root
+    } else {
+        root
+    })
+}
+
+/// `base^exponent`, with the exponent in little-endian bytes.
+fn pow(base: &Scalar, exponent: &[u8]) -> Scalar {
+    let mut result = Scalar::one();
+    for byte in exponent.iter().rev() {
+        for bit in (0..8).rev() {
+            result *= result;
+            if (byte >> bit) & 1 == 1 {
+                result *= base;
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bgw::BgwMultiplicationRistretto;
+
+    /// The sub-shares party `recipient` receives from every dealer.
+    fn received(
+        dealt: &[Vec<SubShare<Scalar, RistrettoPoint>>],
+        recipient: usize,
+    ) -> Vec<SubShare<Scalar, RistrettoPoint>> {
+        dealt
+            .iter()
+            .map(|sub_shares| sub_shares[recipient - 1].clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_joint_random() {
+        let joint = JointRandomRistretto {
+            threshold: 3,
+            share_amount: 5,
+        };
+        let dealt: Vec<Vec<SubShare<Scalar, RistrettoPoint>>> =
+            (1..=5).map(|dealer| joint.deal(dealer)).collect();
+        let shares: Vec<JointShare> = (1..=5)
+            .map(|recipient| {
+                joint
+                    .combine(recipient, &received(&dealt, recipient))
+                    .unwrap()
+            })
+            .collect();
+        assert!(shares.iter().all(JointShare::verify));
+        assert!(shares
+            .windows(2)
+            .all(|pair| pair[0].commitments == pair[1].commitments));
+
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: 3,
+            share_amount: 5,
+        };
+        let tuples: Vec<(usize, Scalar)> = shares[2..].iter().map(|s| s.share.into()).collect();
+        let expected: Scalar = dealt
+            .iter()
+            .map(|sub_shares| {
+                let tuples: Vec<(usize, Scalar)> =
+                    sub_shares[..3].iter().map(|sub| sub.share.into()).collect();
+                vss.recover(&tuples)
+            })
+            .sum();
+        assert_eq!(vss.recover(&tuples), expected);
+
+        let mut forged = received(&dealt, 4);
+        forged[1].share.value += Scalar::one();
+        assert!(joint.combine(4, &forged).is_none());
+        assert!(joint.combine(3, &received(&dealt, 4)).is_none());
+        let mut repeated = received(&dealt, 4);
+        repeated[1] = repeated[0].clone();
+        assert!(joint.combine(4, &repeated).is_none());
+    }
+
+    #[test]
+    fn test_random_bit() {
+        let joint = JointRandomRistretto {
+            threshold: 2,
+            share_amount: 3,
+        };
+        let bgw = BgwMultiplicationRistretto {
+            threshold: 2,
+            share_amount: 3,
+        };
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: 2,
+            share_amount: 3,
+        };
+        for _ in 0..8 {
+            let dealt: Vec<Vec<SubShare<Scalar, RistrettoPoint>>> =
+                (1..=3).map(|dealer| joint.deal(dealer)).collect();
+            let r: Vec<JointShare> = (1..=3)
+                .map(|recipient| {
+                    joint
+                        .combine(recipient, &received(&dealt, recipient))
+                        .unwrap()
+                })
+                .collect();
+            let sub_shares: Vec<Vec<SubShare<Scalar, RistrettoPoint>>> = r
+                .iter()
+                .map(|r| bgw.reshare(r.share.into(), r.share.into()).1)
+                .collect();
+            let squares: Vec<(usize, Scalar)> = (1..=3)
+                .map(|recipient| {
+                    bgw.combine(recipient, &received(&sub_shares, recipient))
+                        .unwrap()
+                        .0
+                })
+                .collect();
+            let square = vss.recover(&squares[1..]);
+
+            let bit: Vec<JointShare> = r
+                .iter()
+                .map(|r| JointRandomRistretto::random_bit(r, &square).unwrap())
+                .collect();
+            assert!(bit.iter().all(JointShare::verify));
+            let tuples: Vec<(usize, Scalar)> = bit[..2].iter().map(|s| s.share.into()).collect();
+            let value = vss.recover(&tuples);
+            assert!(value == Scalar::zero() || value == Scalar::one());
+        }
+        // Not a square: 2 is a non-residue modulo l.
+        assert!(sqrt(&Scalar::from(2u64)).is_none());
+        assert_eq!(
+            sqrt(&Scalar::from(9u64)).map(|root| root * root),
+            Some(Scalar::from(9u64))
+        );
+    }
+}
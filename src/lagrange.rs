@@ -0,0 +1,246 @@
+use crate::field::FieldElement;
+
+/// The Lagrange coefficients of a fixed set of share identifiers, evaluated
+/// at a fixed point.
+///
+/// Computing the coefficients needs a single field inversion (Montgomery's
+/// batch inversion trick), and the result can be cached and reused to
+/// interpolate any number of sharings over the same identifiers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LagrangeCoefficients<F: FieldElement> {
+    identifiers: Vec<usize>,
+    coefficients: Vec<F>,
+}
+
+impl<F: FieldElement> LagrangeCoefficients<F> {
+    /// Compute the coefficients of `identifiers` for interpolation at `at`.
+    ///
+    /// The identifiers must be non-empty and pairwise distinct. They are mapped
+    /// with `FieldElement::from_index`, while `at` is a field element: use
+    /// `F::from_index(identifier)` to interpolate at another share's point.
+    pub fn new(identifiers: &[usize], at: F) -> Self {
+        assert!(!identifiers.is_empty());
+        let xs: Vec<F> = identifiers.iter().map(|&x| F::from_index(x)).collect();
+        let n = xs.len();
+
+        // numerator_i = prod_{j != i} (at - x_j), from prefix and suffix products.
+        let mut prefix = vec![F::one(); n + 1];
+        for j in 0..n {
+            prefix[j + 1] = prefix[j] * (at - xs[j]);
+        }
+        let mut suffix = vec![F::one(); n + 1];
+        for j in (0..n).rev() {
+            suffix[j] = suffix[j + 1] * (at - xs[j]);
+        }
+
+        // denominator_i = prod_{j != i} (x_i - x_j), which is zero iff two
+        // identifiers collide.
+        let denominators: Vec<F> = (0..n)
+            .map(|i| {
+                (0..n)
+                    .filter(|&j| j != i)
+                    .fold(F::one(), |product, j| product * (xs[i] - xs[j]))
+            })
+            .collect();
+        assert!(
+            denominators.iter().all(|d| *d != F::zero()),
+            "share identifiers must be distinct"
+        );
+        let inverses = batch_invert(&denominators);
+
+        let coefficients = (0..n)
+            .map(|i| prefix[i] * suffix[i + 1] * inverses[i])
+            .collect();
+        LagrangeCoefficients {
+            identifiers: identifiers.to_vec(),
+            coefficients,
+        }
+    }
+
+    /// The identifiers the coefficients were computed for.
+    pub fn identifiers(&self) -> &[usize] {
+        &self.identifiers
+    }
+
+    /// The coefficients, in the same order as `identifiers()`.
+    pub fn coefficients(&self) -> &[F] {
+        &self.coefficients
+    }
+
+    /// The coefficient of a single identifier, if it belongs to the set.
+    pub fn coefficient(&self, identifier: usize) -> Option<F> {
+        self.identifiers
+            .iter()
+            .position(|&x| x == identifier)
+            .map(|i| self.coefficients[i])
+    }
+
+    /// Interpolate the polynomial through `shares` at the precomputed point.
+    ///
+    /// The shares may come in any order, but their identifiers must be exactly
+    /// the identifiers the coefficients were computed for, each once.
+    pub fn interpolate(&self, shares: &[(usize, F)]) -> F {
+        assert!(shares.len() == self.identifiers.len());
+        let mut indices: Vec<usize> = shares.iter().map(|share| share.0).collect();
+        indices.sort_unstable();
+        indices.dedup();
+        assert!(indices.len() == shares.len(), "duplicate share identifiers");
+        shares.iter().fold(F::zero(), |sum, &(index, value)| {
+            let coefficient = self
+                .coefficient(index)
+                .expect("share identifier outside of the coefficient set");
+            sum + coefficient * value
+        })
+    }
+}
+
+/// Invert every element of `values` with a single field inversion.
+pub(crate) fn batch_invert<F: FieldElement>(values: &[F]) -> Vec<F> {
+    let mut products = Vec::with_capacity(values.len());
+    let mut accumulator = F::one();
+    for value in values {
+        products.push(accumulator);
+        accumulator = accumulator * *value;
+    }
+    let mut inverse = accumulator.invert();
+    let mut inverses = vec![F::zero(); values.len()];
+    for i in (0..values.len()).rev() {
+        inverses[i] = inverse * products[i];
+        inverse = inverse * values[i];
+    }
+    inverses
+}
+
+/// Compute the coefficients (lowest degree first) of the unique polynomial of
+/// degree `xs.len() - 1` going through the points `(xs[i], ys[i])`.
+pub(crate) fn interpolate_polynomial<F: FieldElement>(xs: &[F], ys: &[F]) -> Vec<F> {
+    assert!(!xs.is_empty() && xs.len() == ys.len());
+    let m = xs.len();
+
+    // master(X) = prod_j (X - x_j)
+    let mut master = vec![F::one()];
+    for &x in xs {
+        let mut next = vec![F::zero(); master.len() + 1];
+        for (i, &coefficient) in master.iter().enumerate() {
+            next[i + 1] = next[i + 1] + coefficient;
+            next[i] = next[i] - x * coefficient;
+        }
+        master = next;
+    }
+
+    let denominators: Vec<F> = (0..m)
+        .map(|j| {
+            (0..m)
+                .filter(|&i| i != j)
+                .fold(F::one(), |product, i| product * (xs[j] - xs[i]))
+        })
+        .collect();
+    assert!(
+        denominators.iter().all(|d| *d != F::zero()),
+        "interpolation points must be distinct"
+    );
+    let inverses = batch_invert(&denominators);
+
+    let mut polynomial = vec![F::zero(); m];
+    let mut quotient = vec![F::zero(); m];
+    for j in 0..m {
+        // quotient(X) = master(X) / (X - x_j), by synthetic division.
+        quotient[m - 1] = master[m];
+        for i in (1..m).rev() {
+            quotient[i - 1] = master[i] + xs[j] * quotient[i];
+        }
+        let scale = ys[j] * inverses[j];
+        for i in 0..m {
+            polynomial[i] = polynomial[i] + scale * quotient[i];
+        }
+    }
+    polynomial
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feldman_vss::new_random;
+    use crate::{Secp256k1Scalar, VerifiableSecretSharing, VerifiableSecretSharingRistretto};
+    use curve25519_dalek::scalar::Scalar;
+
+    #[test]
+    fn test_reuse_across_sharings() {
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: 5,
+            share_amount: 10,
+        };
+        let identifiers = [2, 4, 5, 7, 10];
+        let coefficients = LagrangeCoefficients::new(&identifiers, Scalar::zero());
+        for _ in 0..3 {
+            let secret = new_random();
+            let (shares, _) = vss.split(&secret);
+            let subset: Vec<(usize, Scalar)> = identifiers.iter().map(|&i| shares[i - 1]).collect();
+            assert_eq!(coefficients.interpolate(&subset), secret);
+        }
+    }
+
+    #[test]
+    fn test_interpolate_at_share_point() {
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: 3,
+            share_amount: 5,
+        };
+        let (shares, _) = vss.split(&new_random());
+        let coefficients = LagrangeCoefficients::new(&[1, 3, 5], Scalar::from_index(4));
+        let subset = [shares[0], shares[2], shares[4]];
+        assert_eq!(coefficients.interpolate(&subset), shares[3].1);
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicate share identifiers")]
+    fn test_duplicate_shares() {
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: 3,
+            share_amount: 5,
+        };
+        let (shares, _) = vss.split(&new_random());
+        let coefficients = LagrangeCoefficients::new(&[1, 2, 3], Scalar::zero());
+        coefficients.interpolate(&[shares[0], shares[0], shares[1]]);
+    }
+
+    #[test]
+    fn test_secp256k1_matches_recover() {
+        let secret = Secp256k1Scalar::new_random();
+        let vss = VerifiableSecretSharing {
+            threshold: 3,
+            share_amount: 6,
+        };
+        let (shares, _) = vss.split(&secret);
+        let subset = [shares[5], shares[1], shares[3]];
+        let identifiers: Vec<usize> = subset.iter().map(|share| share.0).collect();
+        let coefficients = LagrangeCoefficients::new(&identifiers, Secp256k1Scalar::zero());
+        assert_eq!(coefficients.interpolate(&subset), secret);
+        assert_eq!(vss.recover(&subset), secret);
+    }
+
+    #[test]
+    fn test_interpolate_polynomial() {
+        let polynomial: Vec<Scalar> = (0..6).map(|_| new_random()).collect();
+        let xs: Vec<Scalar> = (0..6u64).map(|x| -Scalar::from(x * x)).collect();
+        let ys: Vec<Scalar> = xs
+            .iter()
+            .map(|x| {
+                polynomial
+                    .iter()
+                    .rev()
+                    .fold(Scalar::zero(), |sum, c| sum * x + c)
+            })
+            .collect();
+        assert_eq!(interpolate_polynomial(&xs, &ys), polynomial);
+    }
+
+    #[test]
+    fn test_batch_invert() {
+        let values: Vec<Scalar> = (1..20u64).map(Scalar::from).collect();
+        let inverses = batch_invert(&values);
+        for (value, inverse) in values.iter().zip(inverses.iter()) {
+            assert_eq!(value * inverse, Scalar::one());
+        }
+    }
+}
@@ -4,10 +4,86 @@
 //! A rust implementation of (verifiable) Shamir Secret Sharing over a finite field.
 //!
 //!
+pub use additive::{additive_commitment, additive_to_shamir, shamir_to_additive};
+pub use beaver::{BeaverMultiplicationRistretto, BeaverTriple, BeaverTripleCommitments};
+pub use bgw::{BgwMultiplicationRistretto, BgwReshare};
+pub use comparison::{
+    ComparisonMask, ComparisonProtocol, ComparisonStep, SecureComparisonRistretto,
+};
 pub use feldman_vss::VerifiableSecretSharingRistretto;
+pub use feldman_vss_bls12_381::{new_random_bls12_381, VerifiableSecretSharingBls12381};
+pub use feldman_vss_bn254::{new_random_bn254, VerifiableSecretSharingBn254};
+pub use feldman_vss_bytes::ByteShare;
+pub use feldman_vss_ed25519::{expand_ed25519_seed, VerifiableSecretSharingEd25519};
+pub use feldman_vss_p256::{new_random_p256, VerifiableSecretSharingP256};
+pub use feldman_vss_pasta::{
+    new_random_pasta, VerifiableSecretSharingPallas, VerifiableSecretSharingVesta,
+};
 pub use feldman_vss_secp256k1::VerifiableSecretSharing;
+pub use field::FieldElement;
+pub use group::GroupElement;
+pub use hierarchical_vss::{
+    HierarchicalSecretSharing, HierarchicalSecretSharingRistretto, HierarchicalShare,
+};
+pub use joint_random::{JointRandomRistretto, JointShare};
+pub use lagrange::LagrangeCoefficients;
+pub use packed_vss::PackedSecretSharingRistretto;
+pub use paillier::{PaillierKeyPair, PaillierPublicKey};
+pub use policy_vss::{
+    Policy, PolicyCommitments, PolicyError, PolicySecretSharingRistretto, PolicyShare,
+};
+pub use prime_field_vss::{PrimeFieldShare, PrimeFieldVss, SchnorrGroup};
+pub use proofs::{DleqProof, ProofGroup, SchnorrProof};
+pub use pvss::{
+    secret_generator, PubliclyVerifiableSecretSharingRistretto, PvssDealing, PvssDecryptedShare,
+};
+pub use scrape::dual_code_check;
 pub use secp256k1_helper::{Secp256k1Point, Secp256k1Scalar};
+pub use shamir_gf256::{Gf256, ShamirGf256Error, ShamirSecretSharingGf256};
+pub use share::{Commitments, Share, SubShare};
+pub use slip39::{Slip39, Slip39Error};
+pub use threshold_bls::{ThresholdBls, BLS_DST_BASIC, BLS_DST_POP};
+#[cfg(feature = "insecure-threshold-ecdsa")]
+pub use threshold_ecdsa::{
+    EcdsaMtaResponse, EcdsaPartialSignature, EcdsaRound1, EcdsaRound3, ThresholdEcdsaError,
+    ThresholdEcdsaSigner,
+};
+pub use threshold_elgamal::{ElGamalCiphertext, PartialDecryption, ThresholdElGamalRistretto};
+pub use threshold_oprf::{OprfEvaluation, ThresholdOprfRistretto, OPRF_CONTEXT};
+pub use weighted_vss::{ShareBundle, WeightedSecretSharingRistretto};
 
+mod additive;
+mod beaver;
+mod bgw;
+mod comparison;
 mod feldman_vss;
+mod feldman_vss_bls12_381;
+mod feldman_vss_bn254;
+mod feldman_vss_bytes;
+mod feldman_vss_ed25519;
+mod feldman_vss_generic;
+mod feldman_vss_p256;
+mod feldman_vss_pasta;
 mod feldman_vss_secp256k1;
+mod field;
+mod group;
+mod hierarchical_vss;
+mod joint_random;
+mod lagrange;
+mod packed_vss;
+mod paillier;
+mod policy_vss;
+mod prime_field_vss;
+mod proofs;
+mod pvss;
+mod scrape;
 mod secp256k1_helper;
+mod shamir_gf256;
+mod share;
+mod slip39;
+mod threshold_bls;
+#[cfg(feature = "insecure-threshold-ecdsa")]
+mod threshold_ecdsa;
+mod threshold_elgamal;
+mod threshold_oprf;
+mod weighted_vss;
@@ -0,0 +1,117 @@
+use crate::feldman_vss::{new_random, VerifiableSecretSharingRistretto};
+use crate::field::FieldElement;
+use crate::lagrange::interpolate_polynomial;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+
+/// The `PackedSecretSharingRistretto` structure, for Franklin–Yung packed
+/// secret sharing: `secret_amount` secrets are embedded in a single
+/// polynomial of degree `threshold + secret_amount - 1`, so each party
+/// receives one share for the whole batch.
+///
+/// The secrets sit at the points `0, -1, ..., -(secret_amount - 1)`, and the
+/// shares at `1..=share_amount`. Any `threshold` shares reveal nothing about
+/// the secrets, and any `threshold + secret_amount` shares recover all of them.
+pub struct PackedSecretSharingRistretto {
+    /// the number of shares that reveal nothing about the secrets.
+    pub threshold: usize,
+    /// the number of secrets packed in each sharing.
+    pub secret_amount: usize,
+    /// the total number of shares.
+    pub share_amount: usize,
+}
+
+impl PackedSecretSharingRistretto {
+    /// Split the secrets into shares and add commitments (of
+    /// `threshold + secret_amount` size) to the packed polynomial.
+    pub fn split(&self, secrets: &[Scalar]) -> (Vec<(usize, Scalar)>, Vec<RistrettoPoint>) {
+        assert!(secrets.len() == self.secret_amount && self.secret_amount > 0);
+        assert!(self.threshold + self.secret_amount <= self.share_amount);
+
+        let polynomial = self.sample_polynomial(secrets);
+        let shares = (1..=self.share_amount)
+            .map(|x| (x, Self::mod_evaluate_at(&polynomial, Scalar::from_index(x))))
+            .collect();
+        let commitments = VerifiableSecretSharingRistretto::generate_commitments(&polynomial);
+        (shares, commitments)
+    }
+
+    /// Recover all the secrets with `threshold + secret_amount` shares.
+    pub fn recover(&self, shares: &[(usize, Scalar)]) -> Vec<Scalar> {
+        assert!(shares.len() == self.threshold + self.secret_amount);
+
+        let xs: Vec<Scalar> = shares
+            .iter()
+            .map(|share| Scalar::from_index(share.0))
+            .collect();
+        let ys: Vec<Scalar> = shares.iter().map(|share| share.1).collect();
+        let polynomial = interpolate_polynomial(&xs, &ys);
+        (0..self.secret_amount)
+            .map(|j| Self::mod_evaluate_at(&polynomial, Self::secret_point(j)))
+            .collect()
+    }
+
+    /// Verify that a specific share is valid against the packed polynomial's
+    /// commitments.
+    pub fn verify(share: (usize, Scalar), commitments: &[RistrettoPoint]) -> bool {
+        VerifiableSecretSharingRistretto::verify(share, commitments)
+    }
+
+    /// Verify that a set of shares are valid.
+    pub fn verify_all(shares: &[(usize, Scalar)], commitments: &[RistrettoPoint]) -> bool {
+        VerifiableSecretSharingRistretto::verify_all(shares, commitments)
+    }
+
+    /// The evaluation point of the `j`-th secret, `-j`.
+    fn secret_point(j: usize) -> Scalar {
+        -Scalar::from_index(j)
+    }
+
+    /// Interpolate the secrets at `0, ..., -(k - 1)` together with `threshold`
+    /// random values at `-k, ..., -(k + threshold - 1)`, away from the share
+    /// identifiers.
+    fn sample_polynomial(&self, secrets: &[Scalar]) -> Vec<Scalar> {
+        let xs: Vec<Scalar> = (0..self.threshold + self.secret_amount)
+            .map(Self::secret_point)
+            .collect();
+        let mut ys = secrets.to_vec();
+        ys.extend((0..self.threshold).map(|_| new_random()));
+        interpolate_polynomial(&xs, &ys)
+    }
+
+    fn mod_evaluate_at(polynomial: &[Scalar], x: Scalar) -> Scalar {
+        polynomial
+            .iter()
+            .rev()
+            .fold(Scalar::zero(), |sum, item| x * sum + *item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integration() {
+        let secrets: Vec<Scalar> = (0..8).map(|_| new_random()).collect();
+        let pss = PackedSecretSharingRistretto {
+            threshold: 4,
+            secret_amount: 8,
+            share_amount: 20,
+        };
+        let (shares, commitments) = pss.split(&secrets);
+        assert_eq!(commitments.len(), 12);
+        assert!(PackedSecretSharingRistretto::verify_all(
+            &shares,
+            &commitments
+        ));
+        assert_eq!(pss.recover(&shares[5..17]), secrets);
+
+        let mut tampered = shares[3];
+        tampered.1 += Scalar::one();
+        assert!(!PackedSecretSharingRistretto::verify(
+            tampered,
+            &commitments
+        ));
+    }
+}
@@ -0,0 +1,117 @@
+use num_bigint_dig::{BigUint, ModInverse, RandBigInt, RandPrime};
+use num_integer::Integer;
+use num_traits::One;
+use rand::thread_rng;
+
+/// A Paillier public key, with the generator `n + 1`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaillierPublicKey {
+    /// the modulus `n = p * q`.
+    pub n: BigUint,
+}
+
+/// A Paillier key pair.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaillierKeyPair {
+    /// the public key.
+    pub public_key: PaillierPublicKey,
+    phi: BigUint,
+    phi_inverse: BigUint,
+}
+
+impl PaillierKeyPair {
+    /// Generate a key pair with a modulus of `modulus_bits` bits. A modulus of
+    /// at least 2048 bits should be used outside of tests.
+    pub fn generate(modulus_bits: usize) -> PaillierKeyPair {
+        assert!(modulus_bits >= 1024);
+
+        let mut rng = thread_rng();
+        loop {
+            let p = rng.gen_prime(modulus_bits / 2);
+            let q = rng.gen_prime(modulus_bits - modulus_bits / 2);
+            let n = &p * &q;
+            if p == q || n.bits() != modulus_bits {
+                continue;
+            }
+            let phi = (&p - 1u32) * (&q - 1u32);
+            if let Some(phi_inverse) = phi.clone().mod_inverse(&n) {
+                return PaillierKeyPair {
+                    public_key: PaillierPublicKey { n },
+                    phi,
+                    phi_inverse: phi_inverse.to_biguint().unwrap(),
+                };
+            }
+        }
+    }
+
+    /// Decrypt a ciphertext into a plaintext in `[0, n)`. Returns `None` if
+    /// the ciphertext is not a unit modulo `n^2`, as no encryption is.
+    pub fn decrypt(&self, ciphertext: &BigUint) -> Option<BigUint> {
+        let n = &self.public_key.n;
+        let nn = n * n;
+        if *ciphertext >= nn || !ciphertext.gcd(n).is_one() {
+            return None;
+        }
+        // c^phi = 1 + (m * phi) n mod n^2
+        let l = (ciphertext.modpow(&self.phi, &nn) - 1u32) / n;
+        Some((l * &self.phi_inverse) % n)
+    }
+}
+
+impl PaillierPublicKey {
+    /// Encrypt a plaintext in `[0, n)`.
+    pub fn encrypt(&self, plaintext: &BigUint) -> BigUint {
+        let nn = &self.n * &self.n;
+        let r = loop {
+            let r = thread_rng().gen_biguint_below(&self.n);
+            if r.gcd(&self.n).is_one() {
+                break r;
+            }
+        };
+        // (1 + n)^m = 1 + m n mod n^2
+        let gm = (plaintext * &self.n + 1u32) % &nn;
+        (gm * r.modpow(&self.n, &nn)) % nn
+    }
+
+    /// Homomorphically add two ciphertexts.
+    pub fn add(&self, left: &BigUint, right: &BigUint) -> BigUint {
+        (left * right) % (&self.n * &self.n)
+    }
+
+    /// Homomorphically multiply a ciphertext by a plaintext scalar.
+    pub fn mul(&self, ciphertext: &BigUint, scalar: &BigUint) -> BigUint {
+        ciphertext.modpow(scalar, &(&self.n * &self.n))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_homomorphism() {
+        let key = PaillierKeyPair::generate(1024);
+        let public_key = &key.public_key;
+        let (a, b, c) = (
+            BigUint::from(123_456_789u64),
+            BigUint::from(987_654_321u64),
+            BigUint::from(42u32),
+        );
+        let encrypted = public_key.add(
+            &public_key.mul(&public_key.encrypt(&a), &b),
+            &public_key.encrypt(&c),
+        );
+        assert_eq!(key.decrypt(&encrypted), Some(a * b + c));
+    }
+
+    #[test]
+    fn test_invalid_ciphertexts() {
+        let key = PaillierKeyPair::generate(1024);
+        let n = &key.public_key.n;
+        let valid = key.public_key.encrypt(&BigUint::from(7u32));
+        assert_eq!(key.decrypt(&BigUint::from(0u32)), None);
+        assert_eq!(key.decrypt(n), None);
+        assert_eq!(key.decrypt(&(n * n)), None);
+        assert_eq!(key.decrypt(&(&valid + n * n)), None);
+    }
+}
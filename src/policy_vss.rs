@@ -0,0 +1,536 @@
+use crate::feldman_vss::VerifiableSecretSharingRistretto;
+use crate::lagrange::LagrangeCoefficients;
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// A monotone access policy over named parties.
+///
+/// Policies are written with `AND`, `OR`, parentheses and threshold gates
+/// `k-of {p1, ..., pn}`, e.g. `(A AND B) OR (2-of {C, D, E})`. `AND` and `OR`
+/// are parsed as n-of-n and 1-of-n gates, and `AND` binds tighter than `OR`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Policy {
+    /// A single party.
+    Party(String),
+    /// At least `k` of the sub-policies must be satisfied.
+    Threshold(usize, Vec<Policy>),
+}
+
+/// Errors raised while parsing a policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyError {
+    /// A character that can not start a token, at the given byte offset.
+    UnexpectedCharacter(usize),
+    /// A token that does not fit the grammar, at the given byte offset.
+    UnexpectedToken(usize),
+    /// The policy ended in the middle of an expression.
+    UnexpectedEnd,
+    /// A `k-of` gate with `k` zero or above its number of sub-policies, at the
+    /// given byte offset.
+    InvalidThreshold(usize),
+}
+
+impl fmt::Display for PolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PolicyError::UnexpectedCharacter(at) => write!(f, "unexpected character at {}", at),
+            PolicyError::UnexpectedToken(at) => write!(f, "unexpected token at {}", at),
+            PolicyError::UnexpectedEnd => write!(f, "unexpected end of policy"),
+            PolicyError::InvalidThreshold(at) => write!(f, "invalid threshold at {}", at),
+        }
+    }
+}
+
+impl std::error::Error for PolicyError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Name(String),
+    Of(usize),
+    And,
+    Or,
+    LeftParen,
+    RightParen,
+    LeftBrace,
+    RightBrace,
+    Comma,
+}
+
+fn tokenize(policy: &str) -> Result<Vec<(usize, Token)>, PolicyError> {
+    let mut tokens = Vec::new();
+    let mut chars = policy.char_indices().peekable();
+    while let Some(&(at, c)) = chars.peek() {
+        let token = match c {
+            c if c.is_whitespace() => {
+                chars.next();
+                continue;
+            }
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            '{' => Token::LeftBrace,
+            '}' => Token::RightBrace,
+            ',' => Token::Comma,
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut word = String::new();
+                while let Some(&(_, c)) = chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_' || c == '-') {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                tokens.push((at, word_token(word)));
+                continue;
+            }
+            _ => return Err(PolicyError::UnexpectedCharacter(at)),
+        };
+        chars.next();
+        tokens.push((at, token));
+    }
+    Ok(tokens)
+}
+
+fn word_token(word: String) -> Token {
+    if word.eq_ignore_ascii_case("and") {
+        return Token::And;
+    }
+    if word.eq_ignore_ascii_case("or") {
+        return Token::Or;
+    }
+    let lowercase = word.to_ascii_lowercase();
+    if let Some(Ok(k)) = lowercase.strip_suffix("-of").map(str::parse) {
+        return Token::Of(k);
+    }
+    Token::Name(word)
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(_, token)| token)
+    }
+
+    fn next(&mut self) -> Result<(usize, Token), PolicyError> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .cloned()
+            .ok_or(PolicyError::UnexpectedEnd)?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), PolicyError> {
+        let (at, token) = self.next()?;
+        if token == expected {
+            Ok(())
+        } else {
+            Err(PolicyError::UnexpectedToken(at))
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Policy, PolicyError> {
+        let mut operands = vec![self.parse_and()?];
+        while self.peek() == Some(&Token::Or) {
+            self.position += 1;
+            operands.push(self.parse_and()?);
+        }
+        Ok(gate(1, operands))
+    }
+
+    fn parse_and(&mut self) -> Result<Policy, PolicyError> {
+        let mut operands = vec![self.parse_atom()?];
+        while self.peek() == Some(&Token::And) {
+            self.position += 1;
+            operands.push(self.parse_atom()?);
+        }
+        let n = operands.len();
+        Ok(gate(n, operands))
+    }
+
+    fn parse_atom(&mut self) -> Result<Policy, PolicyError> {
+        match self.next()? {
+            (_, Token::Name(name)) => Ok(Policy::Party(name)),
+            (_, Token::LeftParen) => {
+                let policy = self.parse_or()?;
+                self.expect(Token::RightParen)?;
+                Ok(policy)
+            }
+            (at, Token::Of(k)) => {
+                self.expect(Token::LeftBrace)?;
+                let mut operands = vec![self.parse_or()?];
+                while self.peek() == Some(&Token::Comma) {
+                    self.position += 1;
+                    operands.push(self.parse_or()?);
+                }
+                self.expect(Token::RightBrace)?;
+                if k == 0 || k > operands.len() {
+                    return Err(PolicyError::InvalidThreshold(at));
+                }
+                Ok(Policy::Threshold(k, operands))
+            }
+            (at, _) => Err(PolicyError::UnexpectedToken(at)),
+        }
+    }
+}
+
+/// A gate with a single operand is the operand itself.
+fn gate(k: usize, mut operands: Vec<Policy>) -> Policy {
+    if operands.len() == 1 {
+        operands.pop().unwrap()
+    } else {
+        Policy::Threshold(k, operands)
+    }
+}
+
+impl FromStr for Policy {
+    type Err = PolicyError;
+
+    fn from_str(policy: &str) -> Result<Policy, PolicyError> {
+        let mut parser = Parser {
+            tokens: tokenize(policy)?,
+            position: 0,
+        };
+        let parsed = parser.parse_or()?;
+        match parser.tokens.get(parser.position) {
+            None => Ok(parsed),
+            Some((at, _)) => Err(PolicyError::UnexpectedToken(*at)),
+        }
+    }
+}
+
+impl Policy {
+    /// Whether a set of parties is authorized by the policy.
+    pub fn is_satisfied_by(&self, parties: &[&str]) -> bool {
+        match self {
+            Policy::Party(name) => parties.contains(&name.as_str()),
+            Policy::Threshold(k, operands) => {
+                operands
+                    .iter()
+                    .filter(|operand| operand.is_satisfied_by(parties))
+                    .count()
+                    >= *k
+            }
+        }
+    }
+}
+
+/// A share of a policy sharing, held by `party` for the leaf at `path`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolicyShare {
+    /// the party holding the share.
+    pub party: String,
+    /// the position of the leaf in the policy tree, as child indices from the root.
+    pub path: Vec<usize>,
+    /// the share value.
+    pub value: Scalar,
+}
+
+/// The commitments of every threshold gate of a policy sharing, by path. A
+/// policy made of a single party has the commitment to the secret instead.
+pub type PolicyCommitments = BTreeMap<Vec<usize>, Vec<RistrettoPoint>>;
+
+/// The `PolicySecretSharingRistretto` structure, for secret sharing under a
+/// general monotone access policy (Benaloh–Leichter).
+///
+/// The policy tree is compiled into nested Feldman sharings: each k-of-n gate
+/// splits the value it receives into n shares with threshold k, and each leaf
+/// share goes to its party. A party appearing in several leaves receives one
+/// share per leaf.
+pub struct PolicySecretSharingRistretto {
+    /// the access policy.
+    pub policy: Policy,
+}
+
+impl PolicySecretSharingRistretto {
+    /// Split the secret into per-party share sets and add the commitments of
+    /// every gate.
+    pub fn split(
+        &self,
+        secret: &Scalar,
+    ) -> (BTreeMap<String, Vec<PolicyShare>>, PolicyCommitments) {
+        let mut shares: BTreeMap<String, Vec<PolicyShare>> = BTreeMap::new();
+        let mut commitments = BTreeMap::new();
+        Self::split_node(
+            &self.policy,
+            *secret,
+            &mut Vec::new(),
+            &mut shares,
+            &mut commitments,
+        );
+        (shares, commitments)
+    }
+
+    /// Recover the secret from the shares of an authorized set of parties.
+    /// Returns `None` if the shares do not satisfy the policy.
+    pub fn recover(&self, shares: &[PolicyShare]) -> Option<Scalar> {
+        let leaves: BTreeMap<&[usize], Scalar> = shares
+            .iter()
+            .map(|share| (share.path.as_slice(), share.value))
+            .collect();
+        Self::recover_node(&self.policy, &mut Vec::new(), &leaves)
+    }
+
+    /// Verify that a leaf share is valid against the commitments of its gate.
+    /// Returns false if the share is not at a leaf of the policy, or the gate
+    /// does not have one commitment per unit of its threshold.
+    pub fn verify(&self, share: &PolicyShare, commitments: &PolicyCommitments) -> bool {
+        match share.path.split_last() {
+            Some((index, parent)) => match self.node(parent) {
+                Some(Policy::Threshold(k, operands)) => {
+                    operands.get(*index) == Some(&Policy::Party(share.party.clone()))
+                        && commitments.get(parent).is_some_and(|gate| {
+                            *k > 0
+                                && gate.len() == *k
+                                && VerifiableSecretSharingRistretto::verify(
+                                    (index + 1, share.value),
+                                    gate,
+                                )
+                        })
+                }
+                _ => false,
+            },
+            // A policy made of a single party hands out the secret itself.
+            None => {
+                self.policy == Policy::Party(share.party.clone())
+                    && commitments.get(&share.path).is_some_and(|commitment| {
+                        commitment.len() == 1
+                            && commitment[0] == RISTRETTO_BASEPOINT_POINT * share.value
+                    })
+            }
+        }
+    }
+
+    /// Verify that the commitments of nested gates are consistent: every gate
+    /// has one commitment per unit of its threshold, and the secret committed
+    /// by each gate is the share its parent gate committed to.
+    pub fn verify_commitments(&self, commitments: &PolicyCommitments) -> bool {
+        match &self.policy {
+            Policy::Party(_) => commitments
+                .get(&Vec::new())
+                .is_some_and(|commitment| commitment.len() == 1),
+            policy => Self::verify_gate(policy, &mut Vec::new(), commitments),
+        }
+    }
+
+    /// The node of the policy tree at `path`.
+    fn node(&self, path: &[usize]) -> Option<&Policy> {
+        path.iter()
+            .try_fold(&self.policy, |node, &index| match node {
+                Policy::Threshold(_, operands) => operands.get(index),
+                Policy::Party(_) => None,
+            })
+    }
+
+    fn verify_gate(
+        policy: &Policy,
+        path: &mut Vec<usize>,
+        commitments: &PolicyCommitments,
+    ) -> bool {
+        let (k, operands) = match policy {
+            Policy::Party(_) => return true,
+            Policy::Threshold(k, operands) => (*k, operands),
+        };
+        let gate = match commitments.get(path.as_slice()) {
+            Some(gate) if k > 0 && gate.len() == k => gate.clone(),
+            _ => return false,
+        };
+        operands.iter().enumerate().all(|(i, operand)| {
+            path.push(i);
+            let consistent = match operand {
+                Policy::Party(_) => true,
+                Policy::Threshold(..) => {
+                    commitments.get(path.as_slice()).is_some_and(|child| {
+                        child.first()
+                            == Some(&VerifiableSecretSharingRistretto::evaluate_commitments(
+                                &gate,
+                                i + 1,
+                            ))
+                    }) && Self::verify_gate(operand, path, commitments)
+                }
+            };
+            path.pop();
+            consistent
+        })
+    }
+
+    fn split_node(
+        policy: &Policy,
+        secret: Scalar,
+        path: &mut Vec<usize>,
+        shares: &mut BTreeMap<String, Vec<PolicyShare>>,
+        commitments: &mut PolicyCommitments,
+    ) {
+        match policy {
+            Policy::Party(name) => {
+                if path.is_empty() {
+                    commitments.insert(Vec::new(), vec![RISTRETTO_BASEPOINT_POINT * secret]);
+                }
+                shares.entry(name.clone()).or_default().push(PolicyShare {
+                    party: name.clone(),
+                    path: path.clone(),
+                    value: secret,
+                })
+            }
+            Policy::Threshold(k, operands) => {
+                let vss = VerifiableSecretSharingRistretto {
+                    threshold: *k,
+                    share_amount: operands.len(),
+                };
+                let (gate_shares, gate_commitments) = vss.split(&secret);
+                commitments.insert(path.clone(), gate_commitments);
+                for (i, (operand, (_, value))) in operands.iter().zip(gate_shares).enumerate() {
+                    path.push(i);
+                    Self::split_node(operand, value, path, shares, commitments);
+                    path.pop();
+                }
+            }
+        }
+    }
+
+    fn recover_node(
+        policy: &Policy,
+        path: &mut Vec<usize>,
+        leaves: &BTreeMap<&[usize], Scalar>,
+    ) -> Option<Scalar> {
+        match policy {
+            Policy::Party(_) => leaves.get(path.as_slice()).copied(),
+            Policy::Threshold(k, operands) => {
+                let mut recovered = Vec::with_capacity(*k);
+                for (i, operand) in operands.iter().enumerate() {
+                    if recovered.len() == *k {
+                        break;
+                    }
+                    path.push(i);
+                    if let Some(value) = Self::recover_node(operand, path, leaves) {
+                        recovered.push((i + 1, value));
+                    }
+                    path.pop();
+                }
+                if recovered.len() < *k {
+                    return None;
+                }
+                let xs: Vec<usize> = recovered.iter().map(|share| share.0).collect();
+                Some(LagrangeCoefficients::new(&xs, Scalar::zero()).interpolate(&recovered))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feldman_vss::new_random;
+
+    #[test]
+    fn test_parse() {
+        let policy: Policy = "(A AND B) OR (2-of {C, D, E})".parse().unwrap();
+        let party = |name: &str| Policy::Party(name.to_string());
+        assert_eq!(
+            policy,
+            Policy::Threshold(
+                1,
+                vec![
+                    Policy::Threshold(2, vec![party("A"), party("B")]),
+                    Policy::Threshold(2, vec![party("C"), party("D"), party("E")]),
+                ]
+            )
+        );
+        assert_eq!("A and B or C".parse::<Policy>(), "(A AND B) OR C".parse());
+        assert_eq!(
+            "4-of {A, B, C}".parse::<Policy>(),
+            Err(PolicyError::InvalidThreshold(0))
+        );
+        assert_eq!("A AND".parse::<Policy>(), Err(PolicyError::UnexpectedEnd));
+        assert_eq!(
+            "A B".parse::<Policy>(),
+            Err(PolicyError::UnexpectedToken(2))
+        );
+    }
+
+    #[test]
+    fn test_integration() {
+        let secret = new_random();
+        let pss = PolicySecretSharingRistretto {
+            policy: "(A AND B) OR (2-of {C, D, A AND E})".parse().unwrap(),
+        };
+        let (shares, commitments) = pss.split(&secret);
+        assert!(pss.verify_commitments(&commitments));
+        assert!(shares
+            .values()
+            .flatten()
+            .all(|share| pss.verify(share, &commitments)));
+        assert_eq!(shares["A"].len(), 2);
+
+        let collect = |parties: &[&str]| -> Vec<PolicyShare> {
+            parties
+                .iter()
+                .flat_map(|party| shares[*party].iter().cloned())
+                .collect()
+        };
+        for parties in [&["A", "B"][..], &["C", "D"], &["A", "E", "C"]] {
+            assert!(pss.policy.is_satisfied_by(parties));
+            assert_eq!(pss.recover(&collect(parties)), Some(secret));
+        }
+        for parties in [&["A", "C"][..], &["B", "E", "D"], &["E"]] {
+            assert!(!pss.policy.is_satisfied_by(parties));
+            assert_eq!(pss.recover(&collect(parties)), None);
+        }
+    }
+
+    #[test]
+    fn test_single_party() {
+        let secret = new_random();
+        let pss = PolicySecretSharingRistretto {
+            policy: "A".parse().unwrap(),
+        };
+        let (shares, commitments) = pss.split(&secret);
+        let share = &shares["A"][0];
+        assert!(pss.verify(share, &commitments));
+        assert_eq!(pss.recover(&shares["A"]), Some(secret));
+
+        let mut tampered = share.clone();
+        tampered.value += Scalar::one();
+        assert!(!pss.verify(&tampered, &commitments));
+        assert!(!pss.verify(share, &PolicyCommitments::new()));
+    }
+
+    #[test]
+    fn test_malformed_commitments() {
+        let pss = PolicySecretSharingRistretto {
+            policy: "A OR (2-of {B, C, D})".parse().unwrap(),
+        };
+        let (shares, commitments) = pss.split(&new_random());
+        let share = &shares["B"][0];
+        assert!(pss.verify_commitments(&commitments));
+        assert!(pss.verify(share, &commitments));
+
+        // An empty gate, or one with a wrong number of commitments.
+        let mut empty = commitments.clone();
+        empty.insert(vec![1], Vec::new());
+        assert!(!pss.verify_commitments(&empty));
+        assert!(!pss.verify(share, &empty));
+        let mut long = commitments.clone();
+        long.get_mut(&vec![1])
+            .unwrap()
+            .push(RISTRETTO_BASEPOINT_POINT);
+        assert!(!pss.verify_commitments(&long));
+        assert!(!pss.verify(share, &long));
+        let mut short = commitments.clone();
+        short.insert(Vec::new(), Vec::new());
+        assert!(!pss.verify_commitments(&short));
+
+        // A share at a path that is not a leaf of the policy.
+        let mut misplaced = share.clone();
+        misplaced.path = vec![1];
+        assert!(!pss.verify(&misplaced, &commitments));
+    }
+}
@@ -0,0 +1,253 @@
+use num_bigint_dig::prime::probably_prime;
+use num_bigint_dig::{BigUint, ModInverse, RandBigInt};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand::thread_rng;
+
+/// The number of Miller-Rabin rounds of the primality checks.
+const PRIMALITY_ROUNDS: usize = 20;
+
+/// A Schnorr group: the subgroup of prime order `order` of `Z_modulus^*`,
+/// generated by `generator`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchnorrGroup {
+    /// the prime modulus `p`.
+    pub modulus: BigUint,
+    /// the prime order `q` of the subgroup, dividing `p - 1`.
+    pub order: BigUint,
+    /// the generator `g` of the subgroup.
+    pub generator: BigUint,
+}
+
+impl SchnorrGroup {
+    /// Check the parameters of a group: `modulus` and `order` are prime,
+    /// `order` divides `modulus - 1`, and `generator` has order `order`.
+    pub fn new(modulus: BigUint, order: BigUint, generator: BigUint) -> Option<SchnorrGroup> {
+        let valid = probably_prime(&modulus, PRIMALITY_ROUNDS)
+            && probably_prime(&order, PRIMALITY_ROUNDS)
+            && (&modulus - 1u32).is_multiple_of(&order)
+            && !generator.is_one()
+            && generator < modulus
+            && generator.modpow(&order, &modulus).is_one();
+        if valid {
+            Some(SchnorrGroup {
+                modulus,
+                order,
+                generator,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// The group of quadratic residues modulo a safe prime `p = 2q + 1`,
+    /// generated by `4`. Secrets shared with it live in `Z_q`, not `Z_p`.
+    pub fn from_safe_prime(modulus: BigUint) -> Option<SchnorrGroup> {
+        let order = (&modulus - 1u32) >> 1;
+        SchnorrGroup::new(modulus, order, BigUint::from(4u32))
+    }
+
+    /// Find the smallest modulus `p = 2kq + 1` for a prime order `q`, e.g. a
+    /// Mersenne prime, and a generator of the subgroup of order `q`.
+    pub fn from_order(order: BigUint) -> Option<SchnorrGroup> {
+        if !probably_prime(&order, PRIMALITY_ROUNDS) {
+            return None;
+        }
+        let step = &order << 1;
+        let mut modulus = &step + 1u32;
+        while !probably_prime(&modulus, PRIMALITY_ROUNDS) {
+            modulus += &step;
+        }
+        let cofactor = (&modulus - 1u32) / &order;
+        let mut base = BigUint::from(2u32);
+        loop {
+            let generator = base.modpow(&cofactor, &modulus);
+            if !generator.is_one() {
+                return Some(SchnorrGroup {
+                    modulus,
+                    order,
+                    generator,
+                });
+            }
+            base += 1u32;
+        }
+    }
+
+    /// Raise the generator to an exponent.
+    pub fn exp(&self, exponent: &BigUint) -> BigUint {
+        self.generator.modpow(exponent, &self.modulus)
+    }
+}
+
+/// A share of a `PrimeFieldVss`: the identifier and the value in `Z_q`.
+pub type PrimeFieldShare = (usize, BigUint);
+
+/// The `PrimeFieldVss` structure, for Feldman VSS of secrets in the prime
+/// field `Z_q` of the order of a Schnorr group, with the commitments
+/// `g^a mod p` of multiplicative-group VSS.
+///
+/// The field is that of the group order `q`, not of the modulus `p`: with a
+/// safe prime `p = 2q + 1` secrets must be below `(p - 1) / 2`. To share
+/// secrets modulo a prime `p` itself, use `SchnorrGroup::from_order(p)`.
+pub struct PrimeFieldVss {
+    /// the threshold of shares.
+    pub threshold: usize,
+    /// the total number of shares.
+    pub share_amount: usize,
+    /// the group of the commitments, whose order is the field modulus.
+    pub group: SchnorrGroup,
+}
+
+impl PrimeFieldVss {
+    /// Split the secret into shares and add commitments (of k size).
+    /// Returns `None` if the secret is not below the group order `q`, or if
+    /// `q` is too small to give every party a distinct nonzero identifier.
+    pub fn split(&self, secret: &BigUint) -> Option<(Vec<PrimeFieldShare>, Vec<BigUint>)> {
+        assert!(self.threshold <= self.share_amount);
+        if *secret >= self.group.order || BigUint::from(self.share_amount) >= self.group.order {
+            return None;
+        }
+
+        let mut rng = thread_rng();
+        let mut polynomial = vec![secret.clone()];
+        for _ in 1..self.threshold {
+            polynomial.push(rng.gen_biguint_below(&self.group.order));
+        }
+        let shares = (1..=self.share_amount)
+            .map(|i| (i, self.evaluate_polynomial(&polynomial, i)))
+            .collect();
+        let commitments = polynomial
+            .iter()
+            .map(|coefficient| self.group.exp(coefficient))
+            .collect();
+        Some((shares, commitments))
+    }
+
+    /// Recover the secret with threshold shares.
+    pub fn recover(&self, shares: &[PrimeFieldShare]) -> BigUint {
+        assert!(shares.len() == self.threshold);
+
+        let order = &self.group.order;
+        let xs: Vec<BigUint> = shares.iter().map(|share| share.0 % order).collect();
+        let mut secret = BigUint::zero();
+        for (i, (xi, (_, yi))) in xs.iter().zip(shares).enumerate() {
+            let mut numerator = BigUint::one();
+            let mut denominator = BigUint::one();
+            for (j, xj) in xs.iter().enumerate() {
+                if i != j {
+                    numerator = numerator * xj % order;
+                    denominator = denominator * ((xj + order - xi) % order) % order;
+                }
+            }
+            let inverse = denominator
+                .mod_inverse(order)
+                .and_then(|inverse| inverse.to_biguint())
+                .expect("duplicate share identifiers");
+            secret = (secret + yi * numerator * inverse) % order;
+        }
+        secret
+    }
+
+    /// Verify a share against the commitments.
+    pub fn verify(&self, share: &PrimeFieldShare, commitments: &[BigUint]) -> bool {
+        let modulus = &self.group.modulus;
+        let x = BigUint::from(share.0);
+        let mut iter_rev = commitments.iter().rev();
+        let head = match iter_rev.next() {
+            Some(head) => head.clone(),
+            None => return false,
+        };
+        let evaluated = iter_rev.fold(head, |acc, item| acc.modpow(&x, modulus) * item % modulus);
+        share.1 < self.group.order && self.group.exp(&share.1) == evaluated
+    }
+
+    /// Verify all shares against the commitments.
+    pub fn verify_all(&self, shares: &[PrimeFieldShare], commitments: &[BigUint]) -> bool {
+        shares.iter().all(|share| self.verify(share, commitments))
+    }
+
+    fn evaluate_polynomial(&self, polynomial: &[BigUint], x: usize) -> BigUint {
+        let x = BigUint::from(x);
+        polynomial
+            .iter()
+            .rev()
+            .fold(BigUint::zero(), |acc, coefficient| {
+                (acc * &x + coefficient) % &self.group.order
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint_dig::RandBigInt;
+
+    /// The 2048-bit MODP safe prime of RFC 3526.
+    const MODP_2048: &str = concat!(
+        "FFFFFFFFFFFFFFFFC90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74",
+        "020BBEA63B139B22514A08798E3404DDEF9519B3CD3A431B302B0A6DF25F1437",
+        "4FE1356D6D51C245E485B576625E7EC6F44C42E9A637ED6B0BFF5CB6F406B7ED",
+        "EE386BFB5A899FA5AE9F24117C4B1FE649286651ECE45B3DC2007CB8A163BF05",
+        "98DA48361C55D39A69163FA8FD24CF5F83655D23DCA3AD961C62F356208552BB",
+        "9ED529077096966D670C354E4ABC9804F1746C08CA18217C32905E462E36CE3B",
+        "E39E772C180E86039B2783A2EC07A28FB5C55DF06F4C52C9DE2BCBF695581718",
+        "3995497CEA956AE515D2261898FA051015728E5A8AACAA68FFFFFFFFFFFFFFFF"
+    );
+
+    #[test]
+    fn test_safe_prime() {
+        let modulus = BigUint::parse_bytes(MODP_2048.as_bytes(), 16).unwrap();
+        let group = SchnorrGroup::from_safe_prime(modulus).unwrap();
+        let vss = PrimeFieldVss {
+            threshold: 3,
+            share_amount: 5,
+            group,
+        };
+        let secret = thread_rng().gen_biguint_below(&vss.group.order);
+        let (shares, commitments) = vss.split(&secret).unwrap();
+        assert!(vss.verify_all(&shares, &commitments));
+        assert_eq!(vss.recover(&shares[1..4]), secret);
+
+        let forged = (shares[0].0, (&shares[0].1 + 1u32) % &vss.group.order);
+        assert!(!vss.verify(&forged, &commitments));
+
+        // The field is Z_q with q = (p - 1) / 2, so secrets in [q, p) are rejected.
+        assert!(vss.split(&vss.group.order).is_none());
+        assert!(vss.split(&(&vss.group.modulus - 1u32)).is_none());
+        assert!(!vss.verify(&shares[0], &[]));
+    }
+
+    #[test]
+    fn test_mersenne_prime() {
+        let order = (BigUint::one() << 127) - 1u32;
+        let group = SchnorrGroup::from_order(order.clone()).unwrap();
+        assert!(SchnorrGroup::new(group.modulus.clone(), order, group.generator.clone()).is_some());
+        assert!(SchnorrGroup::from_order((BigUint::one() << 128) - 1u32).is_none());
+        let vss = PrimeFieldVss {
+            threshold: 2,
+            share_amount: 3,
+            group,
+        };
+        let secret = BigUint::from(42u32);
+        let (shares, commitments) = vss.split(&secret).unwrap();
+        assert!(vss.verify_all(&shares, &commitments));
+        assert_eq!(vss.recover(&[shares[2].clone(), shares[0].clone()]), secret);
+    }
+
+    #[test]
+    fn test_small_order() {
+        let group = SchnorrGroup::from_order(BigUint::from(7u32)).unwrap();
+        let mut vss = PrimeFieldVss {
+            threshold: 3,
+            share_amount: 7,
+            group,
+        };
+        // The share of identifier 7 would be the secret itself.
+        assert!(vss.split(&BigUint::from(5u32)).is_none());
+
+        vss.share_amount = 6;
+        let (shares, commitments) = vss.split(&BigUint::from(5u32)).unwrap();
+        assert!(vss.verify_all(&shares, &commitments));
+        assert_eq!(vss.recover(&shares[3..]), BigUint::from(5u32));
+    }
+}
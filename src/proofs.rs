@@ -0,0 +1,346 @@
+use crate::feldman_vss::new_random;
+use crate::field::FieldElement;
+use crate::group::{linear_combination, GroupElement};
+use crate::secp256k1_helper::{Secp256k1Point, Secp256k1Scalar};
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+
+/// A group usable in the proofs of this crate: its elements can be absorbed
+/// by a Merlin transcript, and challenges derived from it.
+pub trait ProofGroup<F: FieldElement>: GroupElement<F> {
+    /// The name of the group, separating the transcripts of different groups.
+    const NAME: &'static [u8];
+
+    /// Append the element to the transcript with the given label.
+    fn append_to_transcript(&self, transcript: &mut Transcript, label: &'static [u8]);
+
+    /// Derive a uniformly distributed scalar challenge from the transcript.
+    fn challenge_scalar(transcript: &mut Transcript, label: &'static [u8]) -> F;
+
+    /// Sample a uniformly random scalar, for nonces and batching weights.
+    fn random_scalar() -> F;
+}
+
+impl ProofGroup<Scalar> for RistrettoPoint {
+    const NAME: &'static [u8] = b"ristretto255";
+
+    fn append_to_transcript(&self, transcript: &mut Transcript, label: &'static [u8]) {
+        transcript.append_message(label, self.compress().as_bytes());
+    }
+
+    fn challenge_scalar(transcript: &mut Transcript, label: &'static [u8]) -> Scalar {
+        let mut bytes = [0u8; 64];
+        transcript.challenge_bytes(label, &mut bytes);
+        Scalar::from_bytes_mod_order_wide(&bytes)
+    }
+
+    fn random_scalar() -> Scalar {
+        new_random()
+    }
+}
+
+impl ProofGroup<Secp256k1Scalar> for Secp256k1Point {
+    const NAME: &'static [u8] = b"secp256k1";
+
+    fn append_to_transcript(&self, transcript: &mut Transcript, label: &'static [u8]) {
+        transcript.append_message(label, &self.serialize());
+    }
+
+    fn challenge_scalar(transcript: &mut Transcript, label: &'static [u8]) -> Secp256k1Scalar {
+        // 64 bytes make the bias of the reduction negligible.
+        let mut bytes = [0u8; 64];
+        transcript.challenge_bytes(label, &mut bytes);
+        Secp256k1Scalar::from_bytes_mod_order(&bytes)
+    }
+
+    fn random_scalar() -> Secp256k1Scalar {
+        Secp256k1Scalar::new_random()
+    }
+}
+
+/// A Schnorr proof of knowledge of `x` such that `public == generator * x`,
+/// made non-interactive with a Merlin transcript.
+///
+/// With `public == commitments[0]`, it proves the knowledge of the secret.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SchnorrProof<F, G> {
+    /// the prover's commitment `generator * w`.
+    pub commitment: G,
+    /// the response `w + c * x`.
+    pub response: F,
+}
+
+/// A Chaum–Pedersen proof that `log_{g1} h1 == log_{g2} h2`, made
+/// non-interactive with a Merlin transcript.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DleqProof<F, G> {
+    /// the prover's commitment `g1 * w`.
+    pub commitment1: G,
+    /// the prover's commitment `g2 * w`.
+    pub commitment2: G,
+    /// the response `w + c * x`.
+    pub response: F,
+}
+
+impl<F, G> SchnorrProof<F, G>
+where
+    F: FieldElement,
+    G: ProofGroup<F>,
+{
+    /// Prove the knowledge of `x`, for the public element `generator * x`.
+    pub fn prove(transcript: &mut Transcript, x: &F) -> Self {
+        let w = G::random_scalar();
+        let commitment = G::generator() * w;
+        let c = Self::challenge(transcript, &(G::generator() * *x), &commitment);
+        SchnorrProof {
+            commitment,
+            response: w + c * *x,
+        }
+    }
+
+    /// Verify the proof for `public`, with a transcript in the same state as
+    /// the prover's.
+    pub fn verify(&self, transcript: &mut Transcript, public: &G) -> bool {
+        let c = Self::challenge(transcript, public, &self.commitment);
+        // Secp256k1 has no identity, so both sides go through
+        // `linear_combination`, which stands for it with `None`.
+        G::generator().checked_mul(&self.response)
+            == linear_combination([(&self.commitment, &F::one()), (public, &c)])
+    }
+
+    /// Verify several proofs at once, each with its own transcript, by
+    /// checking a random linear combination of their equations.
+    pub fn batch_verify(transcripts: &mut [Transcript], publics: &[G], proofs: &[Self]) -> bool {
+        if transcripts.len() != proofs.len() || publics.len() != proofs.len() || proofs.is_empty() {
+            return false;
+        }
+        let mut response = F::zero();
+        let mut terms = Vec::with_capacity(2 * proofs.len());
+        for ((transcript, public), proof) in transcripts.iter_mut().zip(publics).zip(proofs) {
+            let c = Self::challenge(transcript, public, &proof.commitment);
+            let weight = G::random_scalar();
+            response = response + weight * proof.response;
+            terms.push((proof.commitment, weight));
+            terms.push((*public, weight * c));
+        }
+        G::generator().checked_mul(&response) == combine(&terms)
+    }
+
+    fn challenge(transcript: &mut Transcript, public: &G, commitment: &G) -> F {
+        transcript.append_message(b"dom-sep", b"schnorr");
+        transcript.append_message(b"group", G::NAME);
+        public.append_to_transcript(transcript, b"public");
+        commitment.append_to_transcript(transcript, b"commitment");
+        G::challenge_scalar(transcript, b"challenge")
+    }
+}
+
+impl<F, G> DleqProof<F, G>
+where
+    F: FieldElement,
+    G: ProofGroup<F>,
+{
+    /// Prove that `h1 == g1 * x` and `h2 == g2 * x`.
+    pub fn prove(transcript: &mut Transcript, g1: &G, h1: &G, g2: &G, h2: &G, x: &F) -> Self {
+        let w = G::random_scalar();
+        let commitment1 = *g1 * w;
+        let commitment2 = *g2 * w;
+        let c = Self::challenge(
+            transcript,
+            &[*g1, *h1, *g2, *h2],
+            &commitment1,
+            &commitment2,
+        );
+        DleqProof {
+            commitment1,
+            commitment2,
+            response: w + c * *x,
+        }
+    }
+
+    /// Verify the proof for the statement `log_{g1} h1 == log_{g2} h2`, with a
+    /// transcript in the same state as the prover's.
+    pub fn verify(&self, transcript: &mut Transcript, g1: &G, h1: &G, g2: &G, h2: &G) -> bool {
+        let c = Self::challenge(
+            transcript,
+            &[*g1, *h1, *g2, *h2],
+            &self.commitment1,
+            &self.commitment2,
+        );
+        g1.checked_mul(&self.response)
+            == linear_combination([(&self.commitment1, &F::one()), (h1, &c)])
+            && g2.checked_mul(&self.response)
+                == linear_combination([(&self.commitment2, &F::one()), (h2, &c)])
+    }
+
+    /// Verify several proofs at once, each with its own transcript and
+    /// statement `[g1, h1, g2, h2]`, by checking a random linear combination
+    /// of their equations.
+    pub fn batch_verify(
+        transcripts: &mut [Transcript],
+        statements: &[[G; 4]],
+        proofs: &[Self],
+    ) -> bool {
+        if transcripts.len() != proofs.len()
+            || statements.len() != proofs.len()
+            || proofs.is_empty()
+        {
+            return false;
+        }
+        let mut left = Vec::with_capacity(2 * proofs.len());
+        let mut right = Vec::with_capacity(4 * proofs.len());
+        for ((transcript, statement), proof) in transcripts.iter_mut().zip(statements).zip(proofs) {
+            let [g1, h1, g2, h2] = *statement;
+            let c = Self::challenge(
+                transcript,
+                statement,
+                &proof.commitment1,
+                &proof.commitment2,
+            );
+            let (weight1, weight2) = (G::random_scalar(), G::random_scalar());
+            left.push((g1, weight1 * proof.response));
+            left.push((g2, weight2 * proof.response));
+            right.push((proof.commitment1, weight1));
+            right.push((h1, weight1 * c));
+            right.push((proof.commitment2, weight2));
+            right.push((h2, weight2 * c));
+        }
+        combine(&left) == combine(&right)
+    }
+
+    fn challenge(
+        transcript: &mut Transcript,
+        statement: &[G; 4],
+        commitment1: &G,
+        commitment2: &G,
+    ) -> F {
+        transcript.append_message(b"dom-sep", b"dleq");
+        transcript.append_message(b"group", G::NAME);
+        for (label, element) in [b"g1", b"h1", b"g2", b"h2"].iter().zip(statement) {
+            element.append_to_transcript(transcript, *label);
+        }
+        commitment1.append_to_transcript(transcript, b"commitment1");
+        commitment2.append_to_transcript(transcript, b"commitment2");
+        G::challenge_scalar(transcript, b"challenge")
+    }
+}
+
+/// The linear combination of `(element, scalar)` terms, `None` standing for
+/// the identity.
+fn combine<F: FieldElement, G: GroupElement<F>>(terms: &[(G, F)]) -> Option<G> {
+    linear_combination(terms.iter().map(|(element, scalar)| (element, scalar)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+
+    #[test]
+    fn test_dleq() {
+        let x = new_random();
+        let g1 = RISTRETTO_BASEPOINT_POINT;
+        let g2 = RISTRETTO_BASEPOINT_POINT * new_random();
+        let (h1, h2) = (g1 * x, g2 * x);
+        let proof = DleqProof::prove(&mut Transcript::new(b"test"), &g1, &h1, &g2, &h2, &x);
+        assert!(proof.verify(&mut Transcript::new(b"test"), &g1, &h1, &g2, &h2));
+        assert!(!proof.verify(&mut Transcript::new(b"other"), &g1, &h1, &g2, &h2));
+        assert!(!proof.verify(&mut Transcript::new(b"test"), &g1, &h1, &g2, &(h2 + g2)));
+
+        let xs = [Secp256k1Scalar::new_random(), Secp256k1Scalar::new_random()];
+        let g1 = Secp256k1Point::generator();
+        let g2 = g1 * Secp256k1Scalar::new_random();
+        let statements: Vec<[Secp256k1Point; 4]> =
+            xs.iter().map(|&x| [g1, g1 * x, g2, g2 * x]).collect();
+        let mut proofs: Vec<DleqProof<Secp256k1Scalar, Secp256k1Point>> = xs
+            .iter()
+            .zip(&statements)
+            .map(|(x, [g1, h1, g2, h2])| {
+                DleqProof::prove(&mut Transcript::new(b"test"), g1, h1, g2, h2, x)
+            })
+            .collect();
+        let transcripts = vec![Transcript::new(b"test"); 2];
+        assert!(DleqProof::batch_verify(
+            &mut transcripts.clone(),
+            &statements,
+            &proofs
+        ));
+        proofs[1].response = proofs[1].response + Secp256k1Scalar::one();
+        assert!(!DleqProof::batch_verify(
+            &mut transcripts.clone(),
+            &statements,
+            &proofs
+        ));
+    }
+
+    #[test]
+    fn test_schnorr_batch() {
+        let secrets: Vec<Scalar> = (0..4).map(|_| new_random()).collect();
+        let publics: Vec<RistrettoPoint> = secrets
+            .iter()
+            .map(|x| RISTRETTO_BASEPOINT_POINT * x)
+            .collect();
+        let mut proofs: Vec<SchnorrProof<Scalar, RistrettoPoint>> = secrets
+            .iter()
+            .map(|x| SchnorrProof::prove(&mut Transcript::new(b"test"), x))
+            .collect();
+        let transcripts = vec![Transcript::new(b"test"); 4];
+        assert!(proofs[2].verify(&mut Transcript::new(b"test"), &publics[2]));
+        assert!(SchnorrProof::batch_verify(
+            &mut transcripts.clone(),
+            &publics,
+            &proofs
+        ));
+        proofs.swap(0, 1);
+        assert!(!SchnorrProof::batch_verify(
+            &mut transcripts.clone(),
+            &publics,
+            &proofs
+        ));
+
+        let x = Secp256k1Scalar::new_random();
+        let proof: SchnorrProof<Secp256k1Scalar, Secp256k1Point> =
+            SchnorrProof::prove(&mut Transcript::new(b"test"), &x);
+        assert!(proof.verify(
+            &mut Transcript::new(b"test"),
+            &(Secp256k1Point::generator() * x)
+        ));
+        assert!(!proof.verify(&mut Transcript::new(b"test"), &Secp256k1Point::generator()));
+    }
+
+    #[test]
+    fn test_zero_response_secp256k1() {
+        // Secp256k1 can not represent `generator * 0`: a zero response must be
+        // rejected instead of panicking.
+        let g = Secp256k1Point::generator();
+        let x = Secp256k1Scalar::new_random();
+        let mut schnorr: SchnorrProof<Secp256k1Scalar, Secp256k1Point> =
+            SchnorrProof::prove(&mut Transcript::new(b"test"), &x);
+        schnorr.response = Secp256k1Scalar::zero();
+        assert!(!schnorr.verify(&mut Transcript::new(b"test"), &(g * x)));
+        assert!(!SchnorrProof::batch_verify(
+            &mut [Transcript::new(b"test")],
+            &[g * x],
+            &[schnorr]
+        ));
+
+        let h = g * Secp256k1Scalar::new_random();
+        let statement = [g, g * x, h, h * x];
+        let mut dleq: DleqProof<Secp256k1Scalar, Secp256k1Point> = DleqProof::prove(
+            &mut Transcript::new(b"test"),
+            &g,
+            &(g * x),
+            &h,
+            &(h * x),
+            &x,
+        );
+        dleq.response = Secp256k1Scalar::zero();
+        assert!(!dleq.verify(&mut Transcript::new(b"test"), &g, &(g * x), &h, &(h * x)));
+        assert!(!DleqProof::batch_verify(
+            &mut [Transcript::new(b"test")],
+            &[statement],
+            &[dleq]
+        ));
+    }
+}
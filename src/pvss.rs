@@ -0,0 +1,275 @@
+use crate::feldman_vss::{new_random, VerifiableSecretSharingRistretto};
+use crate::lagrange::LagrangeCoefficients;
+use crate::proofs::{DleqProof, ProofGroup};
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+use sha2::Sha512;
+
+/// A dealing of Schoenmakers' PVSS: the Feldman commitments, the share of
+/// each participant encrypted under their public key, and the proofs that
+/// the encryptions match the commitments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PvssDealing {
+    /// the commitments to the polynomial, with the Ristretto basepoint.
+    pub commitments: Vec<RistrettoPoint>,
+    /// `public_key_i * share_i`, for each participant `i`.
+    pub encrypted_shares: Vec<(usize, RistrettoPoint)>,
+    /// the DLEQ proofs that `log_g (g * share_i) == log_{public_key_i} encrypted_share_i`.
+    pub proofs: Vec<DleqProof<Scalar, RistrettoPoint>>,
+}
+
+/// A share decrypted by its participant, with a proof of correct decryption.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PvssDecryptedShare {
+    /// the share index.
+    pub index: usize,
+    /// the decrypted share, `secret_generator() * share`.
+    pub share: RistrettoPoint,
+    /// the DLEQ proof that `log_G public_key == log_share encrypted_share`.
+    pub proof: DleqProof<Scalar, RistrettoPoint>,
+}
+
+/// The `PubliclyVerifiableSecretSharingRistretto` structure, for Schoenmakers'
+/// publicly verifiable secret sharing.
+///
+/// Unlike with Feldman VSS, anyone can check a whole dealing, without any
+/// share being revealed. Participants hold key pairs `(x_i, G * x_i)` over the
+/// generator `G = secret_generator()`, and what is recovered is `G * secret`.
+pub struct PubliclyVerifiableSecretSharingRistretto {
+    /// the threshold of shares.
+    pub threshold: usize,
+    /// the total number of shares.
+    pub share_amount: usize,
+}
+
+/// The generator `G` of the participants' keys and of the shared secret,
+/// independent of the Ristretto basepoint used by the commitments.
+pub fn secret_generator() -> RistrettoPoint {
+    RistrettoPoint::hash_from_bytes::<Sha512>(b"secret-sharing-extra pvss generator")
+}
+
+impl PubliclyVerifiableSecretSharingRistretto {
+    /// Generate a participant's key pair `(x, G * x)`.
+    pub fn generate_keypair() -> (Scalar, RistrettoPoint) {
+        let secret_key = new_random();
+        (secret_key, secret_generator() * secret_key)
+    }
+
+    /// Deal the secret to the participants holding `public_keys`, in share
+    /// index order.
+    pub fn deal(&self, secret: &Scalar, public_keys: &[RistrettoPoint]) -> PvssDealing {
+        assert!(public_keys.len() == self.share_amount);
+
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: self.threshold,
+            share_amount: self.share_amount,
+        };
+        let (shares, commitments) = vss.split(secret);
+        let generator = RISTRETTO_BASEPOINT_POINT;
+        let (encrypted_shares, proofs) = shares
+            .iter()
+            .zip(public_keys)
+            .map(|(&(index, value), public_key)| {
+                let encrypted_share = public_key * value;
+                let proof = DleqProof::prove(
+                    &mut Self::dealing_transcript(&commitments, index),
+                    &generator,
+                    &(generator * value),
+                    public_key,
+                    &encrypted_share,
+                    &value,
+                );
+                ((index, encrypted_share), proof)
+            })
+            .unzip();
+        PvssDealing {
+            commitments,
+            encrypted_shares,
+            proofs,
+        }
+    }
+
+    /// Verify a whole dealing, given the participants' public keys.
+    pub fn verify_dealing(&self, dealing: &PvssDealing, public_keys: &[RistrettoPoint]) -> bool {
+        if dealing.commitments.len() != self.threshold
+            || dealing.encrypted_shares.len() != self.share_amount
+            || dealing.proofs.len() != self.share_amount
+            || public_keys.len() != self.share_amount
+        {
+            return false;
+        }
+        let generator = RISTRETTO_BASEPOINT_POINT;
+        dealing
+            .encrypted_shares
+            .iter()
+            .zip(&dealing.proofs)
+            .zip(public_keys)
+            .enumerate()
+            .all(|(i, ((&(index, encrypted_share), proof), public_key))| {
+                let share_commitment = VerifiableSecretSharingRistretto::evaluate_commitments(
+                    &dealing.commitments,
+                    index,
+                );
+                index == i + 1
+                    && proof.verify(
+                        &mut Self::dealing_transcript(&dealing.commitments, index),
+                        &generator,
+                        &share_commitment,
+                        public_key,
+                        &encrypted_share,
+                    )
+            })
+    }
+
+    /// Decrypt the share at `index` with the participant's secret key, and
+    /// prove that the decryption is correct. Returns `None` if the dealing has
+    /// no share at `index`.
+    pub fn decrypt_share(
+        dealing: &PvssDealing,
+        index: usize,
+        secret_key: &Scalar,
+    ) -> Option<PvssDecryptedShare> {
+        let (_, encrypted_share) = *dealing.encrypted_shares.get(index.checked_sub(1)?)?;
+        let share = encrypted_share * secret_key.invert();
+        let proof = DleqProof::prove(
+            &mut Self::decryption_transcript(index),
+            &secret_generator(),
+            &(secret_generator() * secret_key),
+            &share,
+            &encrypted_share,
+            secret_key,
+        );
+        Some(PvssDecryptedShare {
+            index,
+            share,
+            proof,
+        })
+    }
+
+    /// Verify that a share was correctly decrypted by the holder of `public_key`.
+    pub fn verify_decrypted_share(
+        share: &PvssDecryptedShare,
+        public_key: &RistrettoPoint,
+        dealing: &PvssDealing,
+    ) -> bool {
+        match dealing.encrypted_shares.get(share.index.wrapping_sub(1)) {
+            Some(&(index, encrypted_share)) if index == share.index => share.proof.verify(
+                &mut Self::decryption_transcript(share.index),
+                &secret_generator(),
+                public_key,
+                &share.share,
+                &encrypted_share,
+            ),
+            _ => false,
+        }
+    }
+
+    /// Recover `G * secret` with threshold verified decrypted shares.
+    pub fn recover(&self, shares: &[PvssDecryptedShare]) -> RistrettoPoint {
+        assert!(shares.len() == self.threshold);
+
+        let xs: Vec<usize> = shares.iter().map(|share| share.index).collect();
+        let coefficients = LagrangeCoefficients::new(&xs, Scalar::zero());
+        shares
+            .iter()
+            .zip(coefficients.coefficients())
+            .map(|(share, coefficient)| share.share * coefficient)
+            .sum()
+    }
+
+    fn dealing_transcript(commitments: &[RistrettoPoint], index: usize) -> Transcript {
+        let mut transcript = Transcript::new(b"secret-sharing-extra pvss dealing");
+        for commitment in commitments {
+            commitment.append_to_transcript(&mut transcript, b"commitment");
+        }
+        transcript.append_u64(b"index", index as u64);
+        transcript
+    }
+
+    fn decryption_transcript(index: usize) -> Transcript {
+        let mut transcript = Transcript::new(b"secret-sharing-extra pvss decryption");
+        transcript.append_u64(b"index", index as u64);
+        transcript
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integration() {
+        let secret = new_random();
+        let pvss = PubliclyVerifiableSecretSharingRistretto {
+            threshold: 3,
+            share_amount: 5,
+        };
+        let keys: Vec<(Scalar, RistrettoPoint)> = (0..5)
+            .map(|_| PubliclyVerifiableSecretSharingRistretto::generate_keypair())
+            .collect();
+        let public_keys: Vec<RistrettoPoint> = keys.iter().map(|key| key.1).collect();
+
+        let dealing = pvss.deal(&secret, &public_keys);
+        assert!(pvss.verify_dealing(&dealing, &public_keys));
+
+        let decrypted: Vec<PvssDecryptedShare> = [4, 1, 3]
+            .iter()
+            .map(|&i| {
+                PubliclyVerifiableSecretSharingRistretto::decrypt_share(&dealing, i, &keys[i - 1].0)
+                    .unwrap()
+            })
+            .collect();
+        for share in &decrypted {
+            assert!(
+                PubliclyVerifiableSecretSharingRistretto::verify_decrypted_share(
+                    share,
+                    &public_keys[share.index - 1],
+                    &dealing
+                )
+            );
+        }
+        assert_eq!(pvss.recover(&decrypted), secret_generator() * secret);
+    }
+
+    #[test]
+    fn test_rejects_bad_dealing() {
+        let pvss = PubliclyVerifiableSecretSharingRistretto {
+            threshold: 2,
+            share_amount: 3,
+        };
+        let keys: Vec<(Scalar, RistrettoPoint)> = (0..3)
+            .map(|_| PubliclyVerifiableSecretSharingRistretto::generate_keypair())
+            .collect();
+        let public_keys: Vec<RistrettoPoint> = keys.iter().map(|key| key.1).collect();
+        let mut dealing = pvss.deal(&new_random(), &public_keys);
+        dealing.encrypted_shares[1].1 += secret_generator();
+        assert!(!pvss.verify_dealing(&dealing, &public_keys));
+
+        for index in [0, 4] {
+            assert!(PubliclyVerifiableSecretSharingRistretto::decrypt_share(
+                &dealing, index, &keys[0].0
+            )
+            .is_none());
+        }
+        let mut share =
+            PubliclyVerifiableSecretSharingRistretto::decrypt_share(&dealing, 1, &keys[0].0)
+                .unwrap();
+        assert!(
+            PubliclyVerifiableSecretSharingRistretto::verify_decrypted_share(
+                &share,
+                &public_keys[0],
+                &dealing
+            )
+        );
+        share.share += secret_generator();
+        assert!(
+            !PubliclyVerifiableSecretSharingRistretto::verify_decrypted_share(
+                &share,
+                &public_keys[0],
+                &dealing
+            )
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use crate::field::FieldElement;
+use crate::group::{linear_combination, GroupElement};
+use crate::lagrange::batch_invert;
+
+/// The SCRAPE degree check: `share_commitments[i - 1]` commits to the share
+/// of identifier `i`, and they lie on a polynomial of degree below
+/// `threshold` if and only if their inner product with every codeword of the
+/// dual code is the identity.
+///
+/// The dual codeword is `v_i * m(x_i)`, with `v_i = prod_{j != i} 1 / (x_i - x_j)`
+/// and `m` the polynomial of degree below `n - threshold` whose coefficients
+/// are `dual_polynomial`, which must be sampled at random by the verifier.
+///
+/// Panics unless `0 < threshold < n` and `dual_polynomial` has `n - threshold`
+/// coefficients, for `n` the number of share commitments.
+pub fn dual_code_check<F, G>(
+    share_commitments: &[G],
+    threshold: usize,
+    dual_polynomial: &[F],
+) -> bool
+where
+    F: FieldElement,
+    G: GroupElement<F>,
+{
+    let n = share_commitments.len();
+    assert!(threshold > 0 && threshold < n);
+    assert!(dual_polynomial.len() == n - threshold);
+
+    let xs: Vec<F> = (1..=n).map(F::from_index).collect();
+    let denominators: Vec<F> = xs
+        .iter()
+        .enumerate()
+        .map(|(i, &xi)| {
+            xs.iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .fold(F::one(), |product, (_, &xj)| product * (xi - xj))
+        })
+        .collect();
+    let codeword: Vec<F> = batch_invert(&denominators)
+        .into_iter()
+        .zip(&xs)
+        .map(|(v, &x)| {
+            let m = dual_polynomial
+                .iter()
+                .rev()
+                .fold(F::zero(), |sum, item| x * sum + *item);
+            v * m
+        })
+        .collect();
+
+    // Some groups can not represent the identity, so compare the inner product
+    // of the first n - 1 terms with the negation of the last one, with `None`
+    // standing for the identity.
+    let sum = linear_combination(share_commitments[..n - 1].iter().zip(&codeword[..n - 1]));
+    sum == share_commitments[n - 1].checked_mul(&(F::zero() - codeword[n - 1]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::dual_code_check;
+    use crate::feldman_vss::new_random;
+    use crate::feldman_vss::VerifiableSecretSharingRistretto;
+    use crate::feldman_vss_secp256k1::VerifiableSecretSharing;
+    use crate::secp256k1_helper::{Secp256k1Point, Secp256k1Scalar};
+    use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use num_bigint_dig::BigInt;
+
+    #[test]
+    fn test_degree_check() {
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: 3,
+            share_amount: 7,
+        };
+        let (shares, _) = vss.split(&new_random());
+        let mut share_commitments: Vec<RistrettoPoint> = shares
+            .iter()
+            .map(|share| RISTRETTO_BASEPOINT_POINT * share.1)
+            .collect();
+        assert!(vss.verify_degree(&share_commitments));
+
+        // Shares of a degree-3 polynomial do not pass a threshold of 3.
+        let higher = VerifiableSecretSharingRistretto {
+            threshold: 4,
+            share_amount: 7,
+        };
+        let (shares, _) = higher.split(&new_random());
+        let higher_commitments: Vec<RistrettoPoint> = shares
+            .iter()
+            .map(|share| RISTRETTO_BASEPOINT_POINT * share.1)
+            .collect();
+        assert!(higher.verify_degree(&higher_commitments));
+        assert!(!vss.verify_degree(&higher_commitments));
+
+        share_commitments[2] += RISTRETTO_BASEPOINT_POINT;
+        assert!(!vss.verify_degree(&share_commitments));
+    }
+
+    #[test]
+    fn test_degree_check_secp256k1() {
+        let vss = VerifiableSecretSharing {
+            threshold: 4,
+            share_amount: 9,
+        };
+        let (shares, _) = vss.split(&Secp256k1Scalar::new_random());
+        let mut share_commitments: Vec<Secp256k1Point> = shares
+            .iter()
+            .map(|share| Secp256k1Point::generator() * share.1)
+            .collect();
+        assert!(vss.verify_degree(&share_commitments));
+        share_commitments[8] = share_commitments[8] + Secp256k1Point::generator();
+        assert!(!vss.verify_degree(&share_commitments));
+    }
+
+    #[test]
+    fn test_identity_secp256k1() {
+        // A zero dual codeword makes every term the point at infinity, which
+        // secp256k1 can not represent; the check must not panic on it.
+        let share_commitments = vec![Secp256k1Point::generator(); 5];
+        let dual_polynomial = vec![Secp256k1Scalar::zero(); 2];
+        assert!(dual_code_check(&share_commitments, 3, &dual_polynomial));
+
+        // Commitments to shares of f(x) = x: the inner product is infinity.
+        let point =
+            |i: i32| Secp256k1Point::generator() * Secp256k1Scalar::from_bigint(&BigInt::from(i));
+        let share_commitments = [point(1), point(2), point(3)];
+        assert!(dual_code_check(
+            &share_commitments,
+            2,
+            &[Secp256k1Scalar::one()]
+        ));
+
+        // With m(x) = 1 + x the dual codeword is (1, -3, 2), so the partial
+        // sum 6G - 3 * 2G reaches infinity before the last term.
+        let share_commitments = [point(6), point(2), point(1)];
+        let dual_polynomial = [Secp256k1Scalar::one(); 2];
+        assert!(!dual_code_check(&share_commitments, 1, &dual_polynomial));
+    }
+}
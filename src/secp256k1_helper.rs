@@ -5,7 +5,7 @@ use num_integer::Integer;
 use rand::{thread_rng, Rng};
 use secp256k1::constants::{CURVE_ORDER, GENERATOR_X, GENERATOR_Y, SECRET_KEY_SIZE};
 use secp256k1::{PublicKey, Secp256k1, SecretKey, VerifyOnly};
-use std::ops::{Add, Mul, Sub};
+use std::ops::{Add, Mul, Neg, Sub};
 use std::sync::Once;
 
 /// The `Secp256k1Scalar` is a scalar, wrapping the `SecretKey`
@@ -17,53 +17,82 @@ pub struct Secp256k1Scalar(SecretKey);
 pub struct Secp256k1Point(PublicKey);
 
 impl Secp256k1Point {
-    ///
+    /// Returns the standard generator of the secp256k1 group.
     pub fn generator() -> Secp256k1Point {
-        let mut v = vec![4 as u8];
+        let mut v = vec![4_u8];
         v.extend(GENERATOR_X.as_ref());
         v.extend(GENERATOR_Y.as_ref());
         Secp256k1Point(PublicKey::from_slice(&v).unwrap())
     }
 
-    fn add_point(&self, other: &PublicKey) -> Secp256k1Point {
-        Secp256k1Point(self.0.combine(other).unwrap())
+    /// Returns the 33-byte compressed encoding of the point.
+    pub fn serialize(&self) -> [u8; 33] {
+        self.0.serialize()
     }
 
-    fn scalar_mul(&self, other: &Secp256k1Scalar) -> Secp256k1Point {
+    /// Parses a point from its compressed or uncompressed encoding.
+    pub fn from_slice(bytes: &[u8]) -> Option<Secp256k1Point> {
+        PublicKey::from_slice(bytes).ok().map(Secp256k1Point)
+    }
+
+    /// Adds two points, or returns `None` if the sum is the point at
+    /// infinity, which a `Secp256k1Point` can not represent.
+    pub fn checked_add(&self, other: &Secp256k1Point) -> Option<Secp256k1Point> {
+        self.0.combine(&other.0).ok().map(Secp256k1Point)
+    }
+
+    /// Multiplies the point by a scalar, or returns `None` if the scalar is
+    /// zero.
+    pub fn checked_mul(&self, other: &Secp256k1Scalar) -> Option<Secp256k1Point> {
         let mut new_point = *self;
         new_point
             .0
             .mul_assign(get_context(), &other.0[..])
-            .expect("Assignment expected");
-        new_point
+            .ok()
+            .map(|_| new_point)
+    }
+
+    fn add_point(&self, other: &PublicKey) -> Secp256k1Point {
+        Secp256k1Point(self.0.combine(other).unwrap())
+    }
+
+    fn scalar_mul(&self, other: &Secp256k1Scalar) -> Secp256k1Point {
+        self.checked_mul(other).expect("Assignment expected")
     }
 }
 
 impl Secp256k1Scalar {
-    ///
+    /// Samples a uniformly random scalar.
     pub fn new_random() -> Secp256k1Scalar {
         let mut rand_bytes = [0u8; 32];
         thread_rng().fill(&mut rand_bytes[..]);
         Secp256k1Scalar(SecretKey::from_slice(&rand_bytes[..]).unwrap())
     }
 
-    ///
+    /// Returns the zero scalar.
     pub fn zero() -> Secp256k1Scalar {
         let zero_arr = [0u8; 32];
         let zero = unsafe { std::mem::transmute::<[u8; 32], SecretKey>(zero_arr) };
         Secp256k1Scalar(zero)
     }
 
-    ///
+    /// Returns the scalar one.
     pub fn one() -> Secp256k1Scalar {
         Secp256k1Scalar::from_bigint(&BigInt::from(1))
     }
-    fn to_bigint(&self) -> BigInt {
+    fn to_bigint(self) -> BigInt {
         // Scalar is big endian in bitcoin secp256k1 impl
         BigInt::from_bytes_be(Plus, &self.0[..])
     }
 
-    ///
+    /// Returns the 32-byte big-endian encoding of the scalar.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&self.0[..]);
+        bytes
+    }
+
+    /// Returns the order of the secp256k1 group.
     pub fn curve_order() -> BigInt {
         BigInt::from_bytes_be(Plus, &CURVE_ORDER)
     }
@@ -86,7 +115,7 @@ impl Secp256k1Scalar {
         Secp256k1Scalar::from_bigint(&result_bigint_mod)
     }
 
-    ///
+    /// Calculate the inverse of Scalar, modulo the curve order.
     pub fn inv(&self) -> Secp256k1Scalar {
         let element = self.to_bigint();
         let modulus = Secp256k1Scalar::curve_order();
@@ -122,14 +151,14 @@ impl Secp256k1Scalar {
         Secp256k1Scalar::from_bigint(&t)
     }
 
-    ///
+    /// Reduces the scalar modulo the curve order.
     pub fn mod_scalar(&self) -> Secp256k1Scalar {
         let bigint_self = self.to_bigint();
         let mod_bigint_self = bigint_self.mod_floor(&Secp256k1Scalar::curve_order());
         Secp256k1Scalar::from_bigint(&mod_bigint_self)
     }
 
-    ///
+    /// Converts a non-negative `BigInt` below the curve order into a scalar.
     pub fn from_bigint(n: &BigInt) -> Secp256k1Scalar {
         if *n == BigInt::from(0) {
             Secp256k1Scalar::zero()
@@ -143,7 +172,13 @@ impl Secp256k1Scalar {
             Secp256k1Scalar(SecretKey::from_slice(&result_bytes).unwrap())
         }
     }
-    ///
+    /// Interprets big-endian bytes as an integer, reduced modulo the curve order.
+    pub fn from_bytes_mod_order(bytes: &[u8]) -> Secp256k1Scalar {
+        let n = BigInt::from_bytes_be(Plus, bytes);
+        Secp256k1Scalar::from_bigint(&n.mod_floor(&Secp256k1Scalar::curve_order()))
+    }
+
+    /// Parses a scalar from its hexadecimal representation.
     pub fn from_hex(hex: &[u8]) -> Secp256k1Scalar {
         Secp256k1Scalar::from_bigint(&BigInt::parse_bytes(hex, 16).unwrap())
     }
@@ -173,6 +208,16 @@ impl Add<Secp256k1Point> for Secp256k1Point {
     }
 }
 
+impl Neg for Secp256k1Point {
+    type Output = Secp256k1Point;
+    fn neg(self) -> Self::Output {
+        // Negation flips the parity of y, that is the compressed prefix.
+        let mut bytes = self.serialize();
+        bytes[0] ^= 1;
+        Secp256k1Point::from_slice(&bytes).unwrap()
+    }
+}
+
 impl Mul<Secp256k1Scalar> for Secp256k1Point {
     type Output = Secp256k1Point;
     fn mul(self, other: Secp256k1Scalar) -> Self::Output {
@@ -181,11 +226,11 @@ impl Mul<Secp256k1Scalar> for Secp256k1Point {
 }
 
 static mut CONTEXT: Option<Secp256k1<VerifyOnly>> = None;
-///
+/// Returns the shared verification context.
 pub fn get_context() -> &'static Secp256k1<VerifyOnly> {
     static INIT_CONTEXT: Once = Once::new();
     INIT_CONTEXT.call_once(|| unsafe {
         CONTEXT = Some(Secp256k1::verification_only());
     });
-    unsafe { CONTEXT.as_ref().unwrap() }
+    unsafe { (*std::ptr::addr_of!(CONTEXT)).as_ref().unwrap() }
 }
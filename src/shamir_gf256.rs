@@ -0,0 +1,270 @@
+use crate::field::FieldElement;
+use crate::lagrange::LagrangeCoefficients;
+use rand::{thread_rng, Rng};
+use std::error::Error;
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+
+/// An element of GF(2^8), with the AES reduction polynomial x^8 + x^4 + x^3 + x + 1.
+///
+/// Multiplication and inversion run in constant time and use no lookup tables.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct Gf256(pub u8);
+
+impl Gf256 {
+    /// Addition and subtraction coincide in characteristic two.
+    fn add_element(self, other: Gf256) -> Gf256 {
+        Gf256(self.0 ^ other.0)
+    }
+
+    fn mul_element(self, other: Gf256) -> Gf256 {
+        let (mut a, mut b, mut product) = (self.0, other.0, 0u8);
+        for _ in 0..8 {
+            product ^= a & 0u8.wrapping_sub(b & 1);
+            let carry = 0u8.wrapping_sub(a >> 7);
+            a = (a << 1) ^ (0x1b & carry);
+            b >>= 1;
+        }
+        Gf256(product)
+    }
+
+    /// Computes `self^254`, which is the inverse of every non-zero element.
+    fn pow_254(self) -> Gf256 {
+        let square = self * self;
+        let mut result = square;
+        let mut power = square;
+        // self^254 = self^2 * self^4 * ... * self^128
+        for _ in 0..6 {
+            power = power * power;
+            result = result * power;
+        }
+        result
+    }
+}
+
+impl Add<Gf256> for Gf256 {
+    type Output = Gf256;
+    fn add(self, other: Gf256) -> Self::Output {
+        self.add_element(other)
+    }
+}
+
+impl Sub<Gf256> for Gf256 {
+    type Output = Gf256;
+    fn sub(self, other: Gf256) -> Self::Output {
+        self.add_element(other)
+    }
+}
+
+impl Mul<Gf256> for Gf256 {
+    type Output = Gf256;
+    fn mul(self, other: Gf256) -> Self::Output {
+        self.mul_element(other)
+    }
+}
+
+impl FieldElement for Gf256 {
+    fn zero() -> Self {
+        Gf256(0)
+    }
+
+    fn one() -> Self {
+        Gf256(1)
+    }
+
+    fn from_index(index: usize) -> Self {
+        assert!(index < 256);
+        Gf256(index as u8)
+    }
+
+    fn invert(&self) -> Self {
+        assert!(self.0 != 0);
+        self.pow_254()
+    }
+}
+
+/// The `ShamirSecretSharingGf256` structure, for byte-wise Shamir secret
+/// sharing over GF(2^8) (as used by Vault and ssss).
+///
+/// Each byte of the secret is shared with its own polynomial, so shares have
+/// the same length as the secret. There are no commitments: shares can not be
+/// verified.
+pub struct ShamirSecretSharingGf256 {
+    /// the threshold of shares.
+    pub threshold: usize,
+    /// the total number of shares (at most 255).
+    pub share_amount: usize,
+}
+
+/// The errors of `ShamirSecretSharingGf256`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShamirGf256Error {
+    /// The threshold is zero or above the share amount, or the share amount
+    /// is above 255.
+    InvalidParameters,
+    /// A share identifier is not in `1..=255`.
+    InvalidIdentifier(usize),
+    /// Two shares have the same identifier.
+    DuplicateIdentifier(usize),
+    /// The number of shares is not the threshold, or the shares have
+    /// different lengths.
+    InconsistentShares,
+}
+
+impl fmt::Display for ShamirGf256Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShamirGf256Error::InvalidParameters => write!(
+                f,
+                "the threshold must be in 1..=share_amount, and share_amount at most 255"
+            ),
+            ShamirGf256Error::InvalidIdentifier(x) => {
+                write!(f, "share identifier {} is not in 1..=255", x)
+            }
+            ShamirGf256Error::DuplicateIdentifier(x) => {
+                write!(f, "duplicate share identifier {}", x)
+            }
+            ShamirGf256Error::InconsistentShares => {
+                write!(f, "wrong number of shares, or shares of different lengths")
+            }
+        }
+    }
+}
+
+impl Error for ShamirGf256Error {}
+
+impl ShamirSecretSharingGf256 {
+    /// Split the secret into shares.
+    pub fn split(&self, secret: &[u8]) -> Result<Vec<(usize, Vec<u8>)>, ShamirGf256Error> {
+        if self.threshold == 0 || self.threshold > self.share_amount || self.share_amount > 255 {
+            return Err(ShamirGf256Error::InvalidParameters);
+        }
+
+        let mut shares: Vec<(usize, Vec<u8>)> = (1..=self.share_amount)
+            .map(|x| (x, Vec::with_capacity(secret.len())))
+            .collect();
+        let mut polynomial = vec![Gf256(0); self.threshold];
+        for &byte in secret {
+            polynomial[0] = Gf256(byte);
+            for coefficient in polynomial.iter_mut().skip(1) {
+                *coefficient = Gf256(thread_rng().gen());
+            }
+            for (x, share) in shares.iter_mut() {
+                share.push(Self::mod_evaluate_at(&polynomial, *x).0);
+            }
+        }
+        Ok(shares)
+    }
+
+    /// Recover the secret with threshold shares.
+    pub fn recover(&self, shares: &[(usize, Vec<u8>)]) -> Result<Vec<u8>, ShamirGf256Error> {
+        if shares.len() != self.threshold
+            || shares
+                .iter()
+                .any(|share| share.1.len() != shares[0].1.len())
+        {
+            return Err(ShamirGf256Error::InconsistentShares);
+        }
+        let xs: Vec<usize> = shares.iter().map(|share| share.0).collect();
+        Self::check_identifiers(&xs)?;
+        Ok(Self::interpolate(
+            &LagrangeCoefficients::new(&xs, Gf256(0)),
+            shares,
+        ))
+    }
+
+    /// Check that the identifiers are distinct elements of `1..=255`.
+    fn check_identifiers(xs: &[usize]) -> Result<(), ShamirGf256Error> {
+        for (i, &x) in xs.iter().enumerate() {
+            if x == 0 || x > 255 {
+                return Err(ShamirGf256Error::InvalidIdentifier(x));
+            }
+            if xs[..i].contains(&x) {
+                return Err(ShamirGf256Error::DuplicateIdentifier(x));
+            }
+        }
+        Ok(())
+    }
+
+    /// Interpolate every byte position of `shares` with the given coefficients.
+    pub(crate) fn interpolate(
+        coefficients: &LagrangeCoefficients<Gf256>,
+        shares: &[(usize, Vec<u8>)],
+    ) -> Vec<u8> {
+        let length = shares[0].1.len();
+        assert!(shares.iter().all(|share| share.1.len() == length));
+        (0..length)
+            .map(|i| {
+                let points: Vec<(usize, Gf256)> = shares
+                    .iter()
+                    .map(|(x, values)| (*x, Gf256(values[i])))
+                    .collect();
+                coefficients.interpolate(&points).0
+            })
+            .collect()
+    }
+
+    fn mod_evaluate_at(polynomial: &[Gf256], x: usize) -> Gf256 {
+        let x = Gf256::from_index(x);
+        polynomial
+            .iter()
+            .rev()
+            .fold(Gf256(0), |sum, item| x * sum + *item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_arithmetic() {
+        // FIPS-197, section 4.2.
+        assert_eq!(Gf256(0x57) * Gf256(0x83), Gf256(0xc1));
+        assert_eq!(Gf256(0x57) * Gf256(0x13), Gf256(0xfe));
+        for a in 1..=255u8 {
+            assert_eq!(Gf256(a) * Gf256(a).invert(), Gf256(1));
+        }
+    }
+
+    #[test]
+    fn test_integration() {
+        let secret = b"a password, or any other small blob".to_vec();
+        let sss = ShamirSecretSharingGf256 {
+            threshold: 3,
+            share_amount: 5,
+        };
+        let shares = sss.split(&secret).unwrap();
+        assert!(shares.iter().all(|share| share.1.len() == secret.len()));
+        assert_eq!(sss.recover(&shares[0..3]), Ok(secret.clone()));
+        assert_eq!(
+            sss.recover(&[shares[4].clone(), shares[0].clone(), shares[2].clone()]),
+            Ok(secret)
+        );
+    }
+
+    #[test]
+    fn test_invalid_identifiers() {
+        let sss = ShamirSecretSharingGf256 {
+            threshold: 2,
+            share_amount: 3,
+        };
+        let shares = sss.split(b"secret").unwrap();
+        assert_eq!(
+            sss.recover(&[shares[0].clone(), (256, shares[1].1.clone())]),
+            Err(ShamirGf256Error::InvalidIdentifier(256))
+        );
+        assert_eq!(
+            sss.recover(&[shares[0].clone(), shares[0].clone()]),
+            Err(ShamirGf256Error::DuplicateIdentifier(1))
+        );
+        let too_many = ShamirSecretSharingGf256 {
+            threshold: 2,
+            share_amount: 256,
+        };
+        assert_eq!(
+            too_many.split(b"secret"),
+            Err(ShamirGf256Error::InvalidParameters)
+        );
+    }
+}
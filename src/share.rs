@@ -0,0 +1,379 @@
+use crate::field::FieldElement;
+use crate::group::GroupElement;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// A share of a linear sharing, on which linear operations can be carried out
+/// without interaction.
+///
+/// Converts from and into the `(usize, F)` tuples of the `split` functions.
+/// Adding or subtracting shares with different identifiers panics; use
+/// `checked_add` and `checked_sub` to get `None` instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Share<F> {
+    /// the share identifier.
+    pub identifier: usize,
+    /// the share value.
+    pub value: F,
+}
+
+/// The commitments of a Feldman sharing, on which the operations matching
+/// those of `Share` can be carried out.
+///
+/// Secp256k1 can not represent the identity, so adding commitments that
+/// cancel out, like `x - x`, panics there; use `checked_add` and
+/// `checked_sub` to get `None` instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commitments<G>(pub Vec<G>);
+
+/// A dealer's share for a single recipient, sent to it privately along with
+/// the dealer's public commitments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubShare<F, G> {
+    /// the identifier of the dealer.
+    pub dealer: usize,
+    /// the recipient's share of the dealt value.
+    pub share: Share<F>,
+    /// the commitments of the dealer's sharing.
+    pub commitments: Commitments<G>,
+}
+
+impl<F: FieldElement, G: Clone> SubShare<F, G> {
+    /// Split the output of a dealer's `split` into the sub-shares of every
+    /// recipient: `sub_shares[j - 1]` is sent privately to party `j`.
+    pub fn from_split(dealer: usize, split: (Vec<(usize, F)>, Vec<G>)) -> Vec<SubShare<F, G>> {
+        let (shares, commitments) = split;
+        shares
+            .into_iter()
+            .map(|share| SubShare {
+                dealer,
+                share: share.into(),
+                commitments: Commitments(commitments.clone()),
+            })
+            .collect()
+    }
+
+    /// Verify that the sub-share is dealt to `recipient`, and is valid for
+    /// commitments of `threshold` elements.
+    pub fn verify(&self, recipient: usize, threshold: usize) -> bool
+    where
+        G: GroupElement<F>,
+    {
+        self.share.identifier == recipient
+            && self.commitments.0.len() == threshold
+            && self.share.verify(&self.commitments)
+    }
+}
+
+impl<F: FieldElement> Share<F> {
+    /// Add two shares of the same identifier.
+    pub fn checked_add(self, other: Share<F>) -> Option<Share<F>> {
+        self.combine(other, |a, b| a + b)
+    }
+
+    /// Subtract two shares of the same identifier.
+    pub fn checked_sub(self, other: Share<F>) -> Option<Share<F>> {
+        self.combine(other, |a, b| a - b)
+    }
+
+    /// Verify the share against Feldman commitments with the generator of `G`.
+    pub fn verify<G: GroupElement<F>>(&self, commitments: &Commitments<G>) -> bool {
+        // `None` stands for the identity, which secp256k1 can not represent.
+        !commitments.0.is_empty()
+            && G::generator().checked_mul(&self.value)
+                == commitments.checked_evaluate(self.identifier)
+    }
+
+    fn combine(self, other: Share<F>, op: impl Fn(F, F) -> F) -> Option<Share<F>> {
+        if self.identifier != other.identifier {
+            return None;
+        }
+        Some(Share {
+            identifier: self.identifier,
+            value: op(self.value, other.value),
+        })
+    }
+}
+
+impl<F> From<(usize, F)> for Share<F> {
+    fn from(share: (usize, F)) -> Self {
+        Share {
+            identifier: share.0,
+            value: share.1,
+        }
+    }
+}
+
+impl<F> From<Share<F>> for (usize, F) {
+    fn from(share: Share<F>) -> Self {
+        (share.identifier, share.value)
+    }
+}
+
+impl<F: FieldElement> Add for Share<F> {
+    type Output = Share<F>;
+    fn add(self, other: Share<F>) -> Share<F> {
+        self.checked_add(other)
+            .expect("shares of different identifiers")
+    }
+}
+
+impl<F: FieldElement> Sub for Share<F> {
+    type Output = Share<F>;
+    fn sub(self, other: Share<F>) -> Share<F> {
+        self.checked_sub(other)
+            .expect("shares of different identifiers")
+    }
+}
+
+/// Adds a public constant to the shared secret.
+impl<F: FieldElement> Add<F> for Share<F> {
+    type Output = Share<F>;
+    fn add(self, constant: F) -> Share<F> {
+        Share {
+            identifier: self.identifier,
+            value: self.value + constant,
+        }
+    }
+}
+
+/// Multiplies the shared secret by a public scalar.
+impl<F: FieldElement> Mul<F> for Share<F> {
+    type Output = Share<F>;
+    fn mul(self, scalar: F) -> Share<F> {
+        Share {
+            identifier: self.identifier,
+            value: self.value * scalar,
+        }
+    }
+}
+
+impl<G> Commitments<G> {
+    /// Evaluate the committed polynomial "in the exponent" at a share
+    /// identifier, giving the commitment to that share.
+    pub fn evaluate<F>(&self, identifier: usize) -> G
+    where
+        F: FieldElement,
+        G: GroupElement<F>,
+    {
+        self.checked_evaluate(identifier)
+            .expect("the commitment to the share is the identity")
+    }
+
+    /// Add the commitments of two sharings, or return `None` if a sum is the
+    /// identity and the group can not represent it.
+    pub fn checked_add<F>(&self, other: &Commitments<G>) -> Option<Commitments<G>>
+    where
+        F: FieldElement,
+        G: GroupElement<F>,
+    {
+        let (longer, shorter) = if self.0.len() >= other.0.len() {
+            (&self.0, &other.0)
+        } else {
+            (&other.0, &self.0)
+        };
+        let mut sum = longer.clone();
+        for (commitment, item) in sum.iter_mut().zip(shorter) {
+            *commitment = commitment.checked_add(item)?;
+        }
+        Some(Commitments(sum))
+    }
+
+    /// Subtract the commitments of two sharings, or return `None` as for
+    /// `checked_add`.
+    pub fn checked_sub<F>(&self, other: &Commitments<G>) -> Option<Commitments<G>>
+    where
+        F: FieldElement,
+        G: GroupElement<F> + Neg<Output = G>,
+    {
+        self.checked_add(&-other.clone())
+    }
+
+    /// Evaluate the committed polynomial, with `None` for the identity.
+    pub(crate) fn checked_evaluate<F>(&self, identifier: usize) -> Option<G>
+    where
+        F: FieldElement,
+        G: GroupElement<F>,
+    {
+        let x = F::from_index(identifier);
+        self.0.iter().rev().fold(None, |sum, item| match sum {
+            Some(sum) => match sum.checked_mul(&x) {
+                Some(product) => product.checked_add(item),
+                None => Some(*item),
+            },
+            None => Some(*item),
+        })
+    }
+
+    /// The commitments to the shared secret plus a public constant, or `None`
+    /// if there are no commitments or, as for `checked_add`, the new
+    /// commitment to the secret is the identity.
+    pub fn add_constant<F>(&self, constant: F) -> Option<Commitments<G>>
+    where
+        F: FieldElement,
+        G: GroupElement<F>,
+    {
+        let mut commitments = self.0.clone();
+        let secret = commitments.first_mut()?;
+        // A zero constant has no commitment on some groups, and changes nothing.
+        if let Some(offset) = G::generator().checked_mul(&constant) {
+            *secret = secret.checked_add(&offset)?;
+        }
+        Some(Commitments(commitments))
+    }
+}
+
+impl<G> From<Vec<G>> for Commitments<G> {
+    fn from(commitments: Vec<G>) -> Self {
+        Commitments(commitments)
+    }
+}
+
+/// Pointwise addition, matching the addition of shares. The sharings may have
+/// different thresholds.
+impl<G: Copy + Add<Output = G>> Add for Commitments<G> {
+    type Output = Commitments<G>;
+    fn add(self, other: Commitments<G>) -> Commitments<G> {
+        let (mut longer, shorter) = if self.0.len() >= other.0.len() {
+            (self.0, other.0)
+        } else {
+            (other.0, self.0)
+        };
+        for (commitment, item) in longer.iter_mut().zip(shorter) {
+            *commitment = *commitment + item;
+        }
+        Commitments(longer)
+    }
+}
+
+/// Pointwise subtraction, matching the subtraction of shares.
+impl<G: Copy + Add<Output = G> + Neg<Output = G>> Sub for Commitments<G> {
+    type Output = Commitments<G>;
+    fn sub(self, other: Commitments<G>) -> Commitments<G> {
+        self + -other
+    }
+}
+
+/// Negation of every commitment, matching the negation of the shared secret.
+impl<G: Neg<Output = G>> Neg for Commitments<G> {
+    type Output = Commitments<G>;
+    fn neg(self) -> Commitments<G> {
+        Commitments(self.0.into_iter().map(|item| -item).collect())
+    }
+}
+
+/// Scaling of every commitment, matching the scaling of shares.
+impl<F: FieldElement, G: GroupElement<F>> Mul<F> for Commitments<G> {
+    type Output = Commitments<G>;
+    fn mul(self, scalar: F) -> Commitments<G> {
+        Commitments(self.0.into_iter().map(|item| item * scalar).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feldman_vss::{new_random, VerifiableSecretSharingRistretto};
+    use crate::feldman_vss_secp256k1::VerifiableSecretSharing;
+    use crate::secp256k1_helper::Secp256k1Scalar;
+    use curve25519_dalek::ristretto::RistrettoPoint;
+    use curve25519_dalek::scalar::Scalar;
+
+    #[test]
+    fn test_linear_combination() {
+        let (x, y, a, c) = (new_random(), new_random(), new_random(), new_random());
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: 3,
+            share_amount: 5,
+        };
+        let (x_shares, x_commitments) = vss.split(&x);
+        let (y_shares, y_commitments) = vss.split(&y);
+
+        // Shares and commitments of a * x + y + c.
+        let shares: Vec<(usize, Scalar)> = x_shares
+            .iter()
+            .zip(&y_shares)
+            .map(|(&xi, &yi)| (Share::from(xi) * a + Share::from(yi) + c).into())
+            .collect();
+        let commitments: Commitments<RistrettoPoint> = (Commitments(x_commitments) * a
+            + Commitments(y_commitments))
+        .add_constant(c)
+        .unwrap();
+        assert!(VerifiableSecretSharingRistretto::verify_all(
+            &shares,
+            &commitments.0
+        ));
+        assert!(Share::from(shares[1]).verify(&commitments));
+        assert_eq!(vss.recover(&shares[2..]), a * x + y + c);
+
+        let mismatched = Share::from(x_shares[0]).checked_sub(Share::from(y_shares[1]));
+        assert_eq!(mismatched, None);
+        assert_eq!(Commitments::<RistrettoPoint>(vec![]).add_constant(c), None);
+    }
+
+    #[test]
+    fn test_difference() {
+        let (x, y) = (new_random(), new_random());
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: 3,
+            share_amount: 5,
+        };
+        let (x_shares, x_commitments) = vss.split(&x);
+        let (y_shares, y_commitments) = vss.split(&y);
+        let commitments = Commitments(x_commitments.clone()) - Commitments(y_commitments);
+        let shares: Vec<(usize, Scalar)> = x_shares
+            .iter()
+            .zip(&y_shares)
+            .map(|(&xi, &yi)| (Share::from(xi) - Share::from(yi)).into())
+            .collect();
+        assert!(VerifiableSecretSharingRistretto::verify_all(
+            &shares,
+            &commitments.0
+        ));
+        assert_eq!(vss.recover(&shares[..3]), x - y);
+
+        let negated = -Commitments(x_commitments);
+        assert!((Share::from(x_shares[4]) * -Scalar::one()).verify(&negated));
+    }
+
+    #[test]
+    fn test_secp256k1() {
+        let (x, y) = (Secp256k1Scalar::new_random(), Secp256k1Scalar::new_random());
+        let vss = VerifiableSecretSharing {
+            threshold: 2,
+            share_amount: 3,
+        };
+        let (x_shares, x_commitments) = vss.split(&x);
+        let (y_shares, y_commitments) = vss.split(&y);
+        let commitments = Commitments(x_commitments.clone()) + Commitments(y_commitments.clone());
+        let shares: Vec<Share<Secp256k1Scalar>> = x_shares
+            .iter()
+            .zip(&y_shares)
+            .map(|(&xi, &yi)| Share::from(xi) + Share::from(yi))
+            .collect();
+        assert!(shares.iter().all(|share| share.verify(&commitments)));
+        assert!(!(shares[0] + Secp256k1Scalar::one()).verify(&commitments));
+        let tuples: Vec<(usize, Secp256k1Scalar)> =
+            shares[1..].iter().map(|&share| share.into()).collect();
+        assert_eq!(vss.recover(&tuples), x + y);
+
+        // Secp256k1 has no identity: x - x can not be committed to.
+        let x_commitments = Commitments(x_commitments);
+        let y_commitments = Commitments(y_commitments);
+        assert_eq!(x_commitments.checked_sub(&x_commitments), None);
+        let difference = x_commitments.checked_sub(&y_commitments).unwrap();
+        assert_eq!(difference, x_commitments.clone() - y_commitments);
+        let share = Share::from(x_shares[0]) - Share::from(y_shares[0]);
+        assert!(share.verify(&difference));
+        assert!(!Share::from((1, Secp256k1Scalar::zero())).verify(&difference));
+
+        // Adding zero changes nothing, and adding -x commits to the identity.
+        assert_eq!(
+            x_commitments.add_constant(Secp256k1Scalar::zero()),
+            Some(x_commitments.clone())
+        );
+        assert_eq!(
+            x_commitments.add_constant(Secp256k1Scalar::zero() - x),
+            None
+        );
+    }
+}
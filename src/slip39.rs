@@ -0,0 +1,908 @@
+use crate::field::FieldElement;
+use crate::lagrange::LagrangeCoefficients;
+use crate::shamir_gf256::{Gf256, ShamirSecretSharingGf256};
+use hmac::{Hmac, Mac, NewMac};
+use pbkdf2::pbkdf2;
+use rand::{thread_rng, Rng};
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// The official SLIP-0039 wordlist, one word per line, sorted.
+const WORDLIST: &str = include_str!("slip39_wordlist.txt");
+
+/// Bits encoded by a single word.
+const RADIX_BITS: usize = 10;
+
+/// Words holding the identifier, flags, exponent and group and member parameters.
+const HEADER_LENGTH_WORDS: usize = 4;
+
+/// Words holding the RS1024 checksum.
+const CHECKSUM_LENGTH_WORDS: usize = 3;
+
+/// The shortest master secret allowed by the specification.
+const MIN_STRENGTH_BYTES: usize = 16;
+
+/// The maximum number of groups, and of members in each group.
+const MAX_SHARE_COUNT: u8 = 16;
+
+/// The total PBKDF2 iteration count for an iteration exponent of zero.
+const BASE_ITERATION_COUNT: u32 = 10000;
+
+/// The number of rounds of the Feistel network.
+const ROUND_COUNT: u8 = 4;
+
+/// The x-coordinate of the shared secret.
+const SECRET_INDEX: usize = 255;
+
+/// The x-coordinate of the digest share.
+const DIGEST_INDEX: usize = 254;
+
+/// Length of the digest prefix of the digest share.
+const DIGEST_LENGTH_BYTES: usize = 4;
+
+const CUSTOMIZATION_STRING_ORIG: &[u8] = b"shamir";
+const CUSTOMIZATION_STRING_EXTENDABLE: &[u8] = b"shamir_extendable";
+
+/// Errors raised while generating or combining SLIP-0039 mnemonics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Slip39Error {
+    /// The group or member thresholds and counts are not a valid configuration.
+    InvalidParameters(&'static str),
+    /// The master secret is shorter than 128 bits or not a multiple of 16 bits.
+    InvalidMasterSecret,
+    /// The passphrase contains characters other than printable ASCII.
+    InvalidPassphrase,
+    /// A word of a mnemonic is not in the wordlist.
+    UnknownWord(String),
+    /// A mnemonic has too few words, or a value length that can not be encoded.
+    InvalidMnemonicLength,
+    /// A mnemonic has an invalid RS1024 checksum.
+    InvalidChecksum,
+    /// The padding bits of a mnemonic are not zero.
+    InvalidPadding,
+    /// The mnemonics do not belong to the same secret, or are inconsistent.
+    InconsistentShares(&'static str),
+    /// The mnemonics do not meet the group or member thresholds.
+    InsufficientShares,
+    /// The recovered secret does not match its digest.
+    InvalidDigest,
+}
+
+impl fmt::Display for Slip39Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Slip39Error::InvalidParameters(reason) => write!(f, "invalid parameters: {}", reason),
+            Slip39Error::InvalidMasterSecret => write!(
+                f,
+                "the master secret must be at least 128 bits and a multiple of 16 bits"
+            ),
+            Slip39Error::InvalidPassphrase => {
+                write!(f, "the passphrase must contain only printable ASCII")
+            }
+            Slip39Error::UnknownWord(word) => write!(f, "unknown word: {}", word),
+            Slip39Error::InvalidMnemonicLength => write!(f, "invalid mnemonic length"),
+            Slip39Error::InvalidChecksum => write!(f, "invalid mnemonic checksum"),
+            Slip39Error::InvalidPadding => write!(f, "invalid mnemonic padding"),
+            Slip39Error::InconsistentShares(reason) => {
+                write!(f, "inconsistent mnemonics: {}", reason)
+            }
+            Slip39Error::InsufficientShares => write!(f, "insufficient number of mnemonics"),
+            Slip39Error::InvalidDigest => write!(f, "invalid digest of the shared secret"),
+        }
+    }
+}
+
+impl std::error::Error for Slip39Error {}
+
+/// The `Slip39` structure, for splitting a master secret into SLIP-0039
+/// mnemonic shares with two-level (group and member) thresholds.
+pub struct Slip39 {
+    /// the number of groups required to recover the master secret.
+    pub group_threshold: u8,
+    /// the (member threshold, member count) of each group.
+    pub groups: Vec<(u8, u8)>,
+    /// the exponent of the PBKDF2 iteration count, `10000 << iteration_exponent`.
+    pub iteration_exponent: u8,
+    /// whether the identifier is left out of the encryption salt, so that
+    /// further sharings of the same encrypted master secret stay compatible.
+    pub extendable: bool,
+}
+
+/// A single decoded mnemonic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Share {
+    identifier: u16,
+    extendable: bool,
+    iteration_exponent: u8,
+    group_index: u8,
+    group_threshold: u8,
+    group_count: u8,
+    member_index: u8,
+    member_threshold: u8,
+    value: Vec<u8>,
+}
+
+impl Slip39 {
+    /// Split the master secret into groups of mnemonics, encrypting it first
+    /// with the passphrase.
+    pub fn split(
+        &self,
+        master_secret: &[u8],
+        passphrase: &[u8],
+    ) -> Result<Vec<Vec<String>>, Slip39Error> {
+        if master_secret.len() < MIN_STRENGTH_BYTES || !master_secret.len().is_multiple_of(2) {
+            return Err(Slip39Error::InvalidMasterSecret);
+        }
+        check_passphrase(passphrase)?;
+        if self.iteration_exponent >= 16 {
+            return Err(Slip39Error::InvalidParameters(
+                "the iteration exponent must be below 16",
+            ));
+        }
+        if self.groups.is_empty() || self.groups.len() > MAX_SHARE_COUNT as usize {
+            return Err(Slip39Error::InvalidParameters(
+                "there must be between 1 and 16 groups",
+            ));
+        }
+        if self.group_threshold == 0 || self.group_threshold as usize > self.groups.len() {
+            return Err(Slip39Error::InvalidParameters(
+                "the group threshold must be between 1 and the number of groups",
+            ));
+        }
+        for &(member_threshold, member_count) in &self.groups {
+            if member_threshold == 0
+                || member_threshold > member_count
+                || member_count > MAX_SHARE_COUNT
+            {
+                return Err(Slip39Error::InvalidParameters(
+                    "each member threshold must be between 1 and its member count, at most 16",
+                ));
+            }
+            if member_threshold == 1 && member_count > 1 {
+                return Err(Slip39Error::InvalidParameters(
+                    "a member threshold of 1 requires a single member, use 1-of-1 instead",
+                ));
+            }
+        }
+
+        let identifier: u16 = thread_rng().gen::<u16>() & 0x7fff;
+        let encrypted_master_secret = encrypt(
+            master_secret,
+            passphrase,
+            self.iteration_exponent,
+            identifier,
+            self.extendable,
+        );
+        let group_shares = split_secret(
+            self.group_threshold,
+            self.groups.len() as u8,
+            &encrypted_master_secret,
+        );
+
+        Ok(self
+            .groups
+            .iter()
+            .zip(group_shares)
+            .map(
+                |(&(member_threshold, member_count), (group_index, group_secret))| {
+                    split_secret(member_threshold, member_count, &group_secret)
+                        .into_iter()
+                        .map(|(member_index, value)| {
+                            Share {
+                                identifier,
+                                extendable: self.extendable,
+                                iteration_exponent: self.iteration_exponent,
+                                group_index: group_index as u8,
+                                group_threshold: self.group_threshold,
+                                group_count: self.groups.len() as u8,
+                                member_index: member_index as u8,
+                                member_threshold,
+                                value,
+                            }
+                            .to_mnemonic()
+                        })
+                        .collect()
+                },
+            )
+            .collect())
+    }
+
+    /// Recover the master secret from mnemonics meeting the group and member
+    /// thresholds, decrypting it with the passphrase.
+    ///
+    /// A wrong passphrase can not be detected: it yields a different secret.
+    pub fn recover<S: AsRef<str>>(
+        mnemonics: &[S],
+        passphrase: &[u8],
+    ) -> Result<Vec<u8>, Slip39Error> {
+        check_passphrase(passphrase)?;
+        let shares = mnemonics
+            .iter()
+            .map(|mnemonic| Share::from_mnemonic(mnemonic.as_ref()))
+            .collect::<Result<Vec<Share>, Slip39Error>>()?;
+        let first = shares.first().ok_or(Slip39Error::InsufficientShares)?;
+        if !shares.iter().all(|share| {
+            share.identifier == first.identifier
+                && share.extendable == first.extendable
+                && share.iteration_exponent == first.iteration_exponent
+        }) {
+            return Err(Slip39Error::InconsistentShares(
+                "the mnemonics belong to different secrets",
+            ));
+        }
+        if !shares.iter().all(|share| {
+            share.group_threshold == first.group_threshold
+                && share.group_count == first.group_count
+                && share.value.len() == first.value.len()
+        }) {
+            return Err(Slip39Error::InconsistentShares(
+                "the mnemonics have different group parameters or lengths",
+            ));
+        }
+
+        let mut groups: BTreeMap<u8, Vec<&Share>> = BTreeMap::new();
+        for share in &shares {
+            groups.entry(share.group_index).or_default().push(share);
+        }
+        if groups.len() < first.group_threshold as usize {
+            return Err(Slip39Error::InsufficientShares);
+        }
+
+        // Any groups meeting their member threshold may be used, so extra
+        // groups and incomplete groups are skipped once enough are recovered.
+        let mut group_shares = Vec::with_capacity(first.group_threshold as usize);
+        for (group_index, members) in groups {
+            let member_threshold = members[0].member_threshold;
+            if !members
+                .iter()
+                .all(|share| share.member_threshold == member_threshold)
+            {
+                return Err(Slip39Error::InconsistentShares(
+                    "the mnemonics of a group have different member thresholds",
+                ));
+            }
+            let mut member_shares: Vec<(usize, Vec<u8>)> = members
+                .iter()
+                .map(|share| (share.member_index as usize, share.value.clone()))
+                .collect();
+            member_shares.sort();
+            member_shares.dedup();
+            if member_shares.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+                return Err(Slip39Error::InconsistentShares(
+                    "a member index is used by different mnemonics",
+                ));
+            }
+            if member_shares.len() < member_threshold as usize
+                || group_shares.len() == first.group_threshold as usize
+            {
+                continue;
+            }
+            member_shares.truncate(member_threshold as usize);
+            group_shares.push((
+                group_index as usize,
+                recover_secret(member_threshold, &member_shares)?,
+            ));
+        }
+        if group_shares.len() < first.group_threshold as usize {
+            return Err(Slip39Error::InsufficientShares);
+        }
+
+        let encrypted_master_secret = recover_secret(first.group_threshold, &group_shares)?;
+        Ok(decrypt(
+            &encrypted_master_secret,
+            passphrase,
+            first.iteration_exponent,
+            first.identifier,
+            first.extendable,
+        ))
+    }
+}
+
+impl Share {
+    fn to_mnemonic(&self) -> String {
+        let header: u64 = (u64::from(self.identifier) << 25)
+            | (u64::from(self.extendable) << 24)
+            | (u64::from(self.iteration_exponent) << 20)
+            | (u64::from(self.group_index) << 16)
+            | (u64::from(self.group_threshold - 1) << 12)
+            | (u64::from(self.group_count - 1) << 8)
+            | (u64::from(self.member_index) << 4)
+            | u64::from(self.member_threshold - 1);
+        let mut data: Vec<u16> = (0..HEADER_LENGTH_WORDS)
+            .rev()
+            .map(|i| ((header >> (i * RADIX_BITS)) & 1023) as u16)
+            .collect();
+        let value_words = (self.value.len() * 8).div_ceil(RADIX_BITS);
+        data.extend(bytes_to_words(&self.value, value_words));
+        let checksum = rs1024_create_checksum(customization_string(self.extendable), &data);
+        data.extend(checksum);
+
+        let wordlist = wordlist();
+        data.iter()
+            .map(|&index| wordlist[index as usize])
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+
+    fn from_mnemonic(mnemonic: &str) -> Result<Share, Slip39Error> {
+        let wordlist = wordlist();
+        let data = mnemonic
+            .split_whitespace()
+            .map(|word| {
+                let word = word.to_lowercase();
+                wordlist
+                    .binary_search(&word.as_str())
+                    .map(|index| index as u16)
+                    .map_err(|_| Slip39Error::UnknownWord(word))
+            })
+            .collect::<Result<Vec<u16>, Slip39Error>>()?;
+
+        let min_length = HEADER_LENGTH_WORDS
+            + (MIN_STRENGTH_BYTES * 8).div_ceil(RADIX_BITS)
+            + CHECKSUM_LENGTH_WORDS;
+        if data.len() < min_length {
+            return Err(Slip39Error::InvalidMnemonicLength);
+        }
+        let value_words = data.len() - HEADER_LENGTH_WORDS - CHECKSUM_LENGTH_WORDS;
+        let padding = (RADIX_BITS * value_words) % 16;
+        if padding > 8 {
+            return Err(Slip39Error::InvalidMnemonicLength);
+        }
+
+        let header = data[..HEADER_LENGTH_WORDS]
+            .iter()
+            .fold(0u64, |header, &word| {
+                (header << RADIX_BITS) | u64::from(word)
+            });
+        let extendable = (header >> 24) & 1 == 1;
+        if !rs1024_verify_checksum(customization_string(extendable), &data) {
+            return Err(Slip39Error::InvalidChecksum);
+        }
+
+        let value_bytes = (RADIX_BITS * value_words - padding) / 8;
+        let value = words_to_bytes(
+            &data[HEADER_LENGTH_WORDS..data.len() - CHECKSUM_LENGTH_WORDS],
+            value_bytes,
+        )
+        .ok_or(Slip39Error::InvalidPadding)?;
+
+        let share = Share {
+            identifier: (header >> 25) as u16,
+            extendable,
+            iteration_exponent: ((header >> 20) & 15) as u8,
+            group_index: ((header >> 16) & 15) as u8,
+            group_threshold: ((header >> 12) & 15) as u8 + 1,
+            group_count: ((header >> 8) & 15) as u8 + 1,
+            member_index: ((header >> 4) & 15) as u8,
+            member_threshold: (header & 15) as u8 + 1,
+            value,
+        };
+        if share.group_count < share.group_threshold {
+            return Err(Slip39Error::InconsistentShares(
+                "the group threshold exceeds the number of groups",
+            ));
+        }
+        if share.group_index >= share.group_count {
+            return Err(Slip39Error::InconsistentShares(
+                "the group index exceeds the number of groups",
+            ));
+        }
+        Ok(share)
+    }
+}
+
+fn wordlist() -> Vec<&'static str> {
+    WORDLIST.lines().collect()
+}
+
+fn check_passphrase(passphrase: &[u8]) -> Result<(), Slip39Error> {
+    if passphrase.iter().all(|&c| (32..=126).contains(&c)) {
+        Ok(())
+    } else {
+        Err(Slip39Error::InvalidPassphrase)
+    }
+}
+
+fn customization_string(extendable: bool) -> &'static [u8] {
+    if extendable {
+        CUSTOMIZATION_STRING_EXTENDABLE
+    } else {
+        CUSTOMIZATION_STRING_ORIG
+    }
+}
+
+/// Encode `bytes` as a big-endian integer in `word_count` 10-bit words,
+/// left-padded with zero bits.
+fn bytes_to_words(bytes: &[u8], word_count: usize) -> Vec<u16> {
+    let padding = word_count * RADIX_BITS - bytes.len() * 8;
+    let mut words = vec![0u16; word_count];
+    for bit in 0..bytes.len() * 8 {
+        if (bytes[bit / 8] >> (7 - bit % 8)) & 1 == 1 {
+            let position = padding + bit;
+            words[position / RADIX_BITS] |= 1 << (RADIX_BITS - 1 - position % RADIX_BITS);
+        }
+    }
+    words
+}
+
+/// Decode `byte_count` bytes from 10-bit words, requiring zero padding bits.
+fn words_to_bytes(words: &[u16], byte_count: usize) -> Option<Vec<u8>> {
+    let padding = words.len() * RADIX_BITS - byte_count * 8;
+    let bit = |position: usize| {
+        (words[position / RADIX_BITS] >> (RADIX_BITS - 1 - position % RADIX_BITS)) & 1
+    };
+    if (0..padding).any(|position| bit(position) == 1) {
+        return None;
+    }
+    Some(
+        (0..byte_count)
+            .map(|i| (0..8).fold(0u8, |byte, j| (byte << 1) | bit(padding + i * 8 + j) as u8))
+            .collect(),
+    )
+}
+
+fn rs1024_polymod(values: &[u16]) -> u32 {
+    const GENERATOR: [u32; 10] = [
+        0xE0E040, 0x1C1C080, 0x3838100, 0x7070200, 0xE0E0009, 0x1C0C2412, 0x38086C24, 0x3090FC48,
+        0x21B1F890, 0x3F3F120,
+    ];
+    values.iter().fold(1u32, |checksum, &value| {
+        let top = checksum >> 20;
+        let checksum = ((checksum & 0xFFFFF) << 10) ^ u32::from(value);
+        (0..10)
+            .filter(|i| (top >> i) & 1 == 1)
+            .fold(checksum, |checksum, i| checksum ^ GENERATOR[i])
+    })
+}
+
+fn rs1024_create_checksum(customization: &[u8], data: &[u16]) -> Vec<u16> {
+    let mut values: Vec<u16> = customization.iter().map(|&c| u16::from(c)).collect();
+    values.extend_from_slice(data);
+    values.extend_from_slice(&[0; CHECKSUM_LENGTH_WORDS]);
+    let polymod = rs1024_polymod(&values) ^ 1;
+    (0..CHECKSUM_LENGTH_WORDS)
+        .rev()
+        .map(|i| ((polymod >> (RADIX_BITS * i)) & 1023) as u16)
+        .collect()
+}
+
+fn rs1024_verify_checksum(customization: &[u8], data: &[u16]) -> bool {
+    let mut values: Vec<u16> = customization.iter().map(|&c| u16::from(c)).collect();
+    values.extend_from_slice(data);
+    rs1024_polymod(&values) == 1
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn random_bytes(length: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; length];
+    thread_rng().fill(&mut bytes[..]);
+    bytes
+}
+
+fn interpolate(shares: &[(usize, Vec<u8>)], x: usize) -> Vec<u8> {
+    let xs: Vec<usize> = shares.iter().map(|share| share.0).collect();
+    let coefficients = LagrangeCoefficients::new(&xs, Gf256::from_index(x));
+    ShamirSecretSharingGf256::interpolate(&coefficients, shares)
+}
+
+/// Split a secret with the digest share construction of the specification:
+/// the polynomial goes through the secret at 255 and through a digest of it at
+/// 254, and the shares are its values at `0..share_count`.
+fn split_secret(threshold: u8, share_count: u8, secret: &[u8]) -> Vec<(usize, Vec<u8>)> {
+    if threshold == 1 {
+        return (0..share_count as usize)
+            .map(|index| (index, secret.to_vec()))
+            .collect();
+    }
+
+    let random_share_count = threshold as usize - 2;
+    let mut shares: Vec<(usize, Vec<u8>)> = (0..random_share_count)
+        .map(|index| (index, random_bytes(secret.len())))
+        .collect();
+    let random_part = random_bytes(secret.len() - DIGEST_LENGTH_BYTES);
+    let mut digest = hmac_sha256(&random_part, secret)[..DIGEST_LENGTH_BYTES].to_vec();
+    digest.extend_from_slice(&random_part);
+
+    let mut base_shares = shares.clone();
+    base_shares.push((DIGEST_INDEX, digest));
+    base_shares.push((SECRET_INDEX, secret.to_vec()));
+    for index in random_share_count..share_count as usize {
+        shares.push((index, interpolate(&base_shares, index)));
+    }
+    shares
+}
+
+fn recover_secret(threshold: u8, shares: &[(usize, Vec<u8>)]) -> Result<Vec<u8>, Slip39Error> {
+    if threshold == 1 {
+        return Ok(shares[0].1.clone());
+    }
+
+    let secret = interpolate(shares, SECRET_INDEX);
+    let digest_share = interpolate(shares, DIGEST_INDEX);
+    let (digest, random_part) = digest_share.split_at(DIGEST_LENGTH_BYTES);
+    if hmac_sha256(random_part, &secret)[..DIGEST_LENGTH_BYTES] != *digest {
+        return Err(Slip39Error::InvalidDigest);
+    }
+    Ok(secret)
+}
+
+fn round_function(
+    round: u8,
+    passphrase: &[u8],
+    iteration_exponent: u8,
+    salt: &[u8],
+    right: &[u8],
+) -> Vec<u8> {
+    let mut password = vec![round];
+    password.extend_from_slice(passphrase);
+    let mut round_salt = salt.to_vec();
+    round_salt.extend_from_slice(right);
+    let mut output = vec![0u8; right.len()];
+    pbkdf2::<Hmac<Sha256>>(
+        &password,
+        &round_salt,
+        (BASE_ITERATION_COUNT << iteration_exponent) / u32::from(ROUND_COUNT),
+        &mut output,
+    );
+    output
+}
+
+fn salt(identifier: u16, extendable: bool) -> Vec<u8> {
+    if extendable {
+        Vec::new()
+    } else {
+        let mut salt = CUSTOMIZATION_STRING_ORIG.to_vec();
+        salt.extend_from_slice(&identifier.to_be_bytes());
+        salt
+    }
+}
+
+/// Run the four-round Feistel network over the secret, with the rounds in
+/// the given order. Encryption and decryption only differ in the order.
+fn feistel(
+    secret: &[u8],
+    passphrase: &[u8],
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+    rounds: impl Iterator<Item = u8>,
+) -> Vec<u8> {
+    let salt = salt(identifier, extendable);
+    let (left, right) = secret.split_at(secret.len() / 2);
+    let (mut left, mut right) = (left.to_vec(), right.to_vec());
+    for round in rounds {
+        let f = round_function(round, passphrase, iteration_exponent, &salt, &right);
+        let next_right: Vec<u8> = left.iter().zip(f).map(|(l, f)| l ^ f).collect();
+        left = right;
+        right = next_right;
+    }
+    right.extend_from_slice(&left);
+    right
+}
+
+fn encrypt(
+    master_secret: &[u8],
+    passphrase: &[u8],
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+) -> Vec<u8> {
+    feistel(
+        master_secret,
+        passphrase,
+        iteration_exponent,
+        identifier,
+        extendable,
+        0..ROUND_COUNT,
+    )
+}
+
+fn decrypt(
+    encrypted_master_secret: &[u8],
+    passphrase: &[u8],
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+) -> Vec<u8> {
+    feistel(
+        encrypted_master_secret,
+        passphrase,
+        iteration_exponent,
+        identifier,
+        extendable,
+        (0..ROUND_COUNT).rev(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_wordlist() {
+        let wordlist = wordlist();
+        assert_eq!(wordlist.len(), 1 << RADIX_BITS);
+        assert!(wordlist.windows(2).all(|pair| pair[0][..4] < pair[1][..4]));
+    }
+
+    #[test]
+    fn test_vectors() {
+        // Official SLIP-0039 test vectors, all with the passphrase "TREZOR".
+        let valid: [(&[&str], &str); 3] = [
+            (
+                &["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard"],
+                "bb54aac4b89dc868ba37d9cc21b2cece",
+            ),
+            (
+                &[
+                    "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed",
+                    "shadow pistol academic acid actress prayer class unknown daughter sweater depict flip twice unkind craft early superior advocate guest smoking",
+                ],
+                "b43ceb7e57a0ea8766221624d01b0864",
+            ),
+            (
+                &["theory painting academic academic armed sweater year military elder discuss acne wildlife boring employer fused large satoshi bundle carbon diagnose anatomy hamster leaves tracks paces beyond phantom capital marvel lips brave detect luck"],
+                "989baf9dcaad5b10ca33dfd8cc75e42477025dce88ae83e75a230086a0e00e92",
+            ),
+        ];
+        for (mnemonics, master_secret) in valid.iter() {
+            assert_eq!(
+                Slip39::recover(mnemonics, b"TREZOR"),
+                Ok(from_hex(master_secret))
+            );
+        }
+
+        let invalid_checksum = ["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision kidney"];
+        assert_eq!(
+            Slip39::recover(&invalid_checksum, b"TREZOR"),
+            Err(Slip39Error::InvalidChecksum)
+        );
+    }
+
+    /// Re-encode a mnemonic after editing its decoded share.
+    fn edit(mnemonic: &str, f: impl FnOnce(&mut Share)) -> String {
+        let mut share = Share::from_mnemonic(mnemonic).unwrap();
+        f(&mut share);
+        share.to_mnemonic()
+    }
+
+    /// Set the first padding bit of a mnemonic, keeping its checksum valid.
+    fn set_padding_bit(mnemonic: &str) -> String {
+        let wordlist = wordlist();
+        let mut data: Vec<u16> = mnemonic
+            .split(' ')
+            .map(|word| wordlist.binary_search(&word).unwrap() as u16)
+            .collect();
+        data.truncate(data.len() - CHECKSUM_LENGTH_WORDS);
+        data[HEADER_LENGTH_WORDS] |= 1 << (RADIX_BITS - 1);
+        let checksum = rs1024_create_checksum(CUSTOMIZATION_STRING_ORIG, &data);
+        data.extend(checksum);
+        data.iter()
+            .map(|&index| wordlist[index as usize])
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn test_invalid_vectors() {
+        // The invalid cases of the official SLIP-0039 test vectors, derived the
+        // same way from the valid 128-bit and 256-bit vectors above.
+        let single = "duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard";
+        let single_256 = "theory painting academic academic armed sweater year military elder discuss acne wildlife boring employer fused large satoshi bundle carbon diagnose anatomy hamster leaves tracks paces beyond phantom capital marvel lips brave detect luck";
+        let pair = [
+            "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed",
+            "shadow pistol academic acid actress prayer class unknown daughter sweater depict flip twice unkind craft early superior advocate guest smoking",
+        ];
+        let with_second = |f: fn(&mut Share)| vec![pair[0].to_string(), edit(pair[1], f)];
+        let cases: Vec<(&str, Vec<String>, Slip39Error)> = vec![
+            (
+                "invalid padding (128 bits)",
+                vec![set_padding_bit(single)],
+                Slip39Error::InvalidPadding,
+            ),
+            (
+                "invalid padding (256 bits)",
+                vec![set_padding_bit(single_256)],
+                Slip39Error::InvalidPadding,
+            ),
+            (
+                "invalid mnemonic length",
+                vec![single.rsplit_once(' ').unwrap().0.to_string()],
+                Slip39Error::InvalidMnemonicLength,
+            ),
+            (
+                "group threshold exceeding the group count",
+                vec![edit(single, |share| share.group_threshold = 2)],
+                Slip39Error::InconsistentShares("the group threshold exceeds the number of groups"),
+            ),
+            (
+                "basic sharing with too few shares",
+                vec![pair[0].to_string()],
+                Slip39Error::InsufficientShares,
+            ),
+            (
+                "different identifiers",
+                with_second(|share| share.identifier ^= 1),
+                Slip39Error::InconsistentShares("the mnemonics belong to different secrets"),
+            ),
+            (
+                "different iteration exponents",
+                with_second(|share| share.iteration_exponent += 1),
+                Slip39Error::InconsistentShares("the mnemonics belong to different secrets"),
+            ),
+            (
+                "mismatching extendable flags",
+                with_second(|share| share.extendable = true),
+                Slip39Error::InconsistentShares("the mnemonics belong to different secrets"),
+            ),
+            (
+                "mismatching group thresholds",
+                with_second(|share| {
+                    share.group_threshold = 2;
+                    share.group_count = 2;
+                }),
+                Slip39Error::InconsistentShares(
+                    "the mnemonics have different group parameters or lengths",
+                ),
+            ),
+            (
+                "mismatching group counts",
+                with_second(|share| share.group_count = 2),
+                Slip39Error::InconsistentShares(
+                    "the mnemonics have different group parameters or lengths",
+                ),
+            ),
+            (
+                "duplicate member indices",
+                with_second(|share| share.member_index = 2),
+                Slip39Error::InconsistentShares("a member index is used by different mnemonics"),
+            ),
+            (
+                "mismatching member thresholds",
+                with_second(|share| share.member_threshold = 3),
+                Slip39Error::InconsistentShares(
+                    "the mnemonics of a group have different member thresholds",
+                ),
+            ),
+            (
+                "invalid digest",
+                with_second(|share| share.value[0] ^= 1),
+                Slip39Error::InvalidDigest,
+            ),
+        ];
+        assert_eq!(Share::from_mnemonic(pair[0]).unwrap().member_index, 2);
+        for (description, mnemonics, error) in cases {
+            assert_eq!(
+                Slip39::recover(&mnemonics, b"TREZOR"),
+                Err(error),
+                "{}",
+                description
+            );
+        }
+    }
+
+    #[test]
+    fn test_extendable_vectors() {
+        // With the extendable flag the identifier is left out of the salt, so
+        // re-identifying the shares keeps the master secret; without it, the
+        // secret changes.
+        let pair = [
+            "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed",
+            "shadow pistol academic acid actress prayer class unknown daughter sweater depict flip twice unkind craft early superior advocate guest smoking",
+        ];
+        let master_secret = from_hex("b43ceb7e57a0ea8766221624d01b0864");
+        let reencode = |extendable: bool, identifier: u16| -> Vec<String> {
+            pair.iter()
+                .map(|mnemonic| {
+                    edit(mnemonic, |share| {
+                        share.extendable = extendable;
+                        share.identifier = identifier;
+                    })
+                })
+                .collect()
+        };
+        let identifier = Share::from_mnemonic(pair[0]).unwrap().identifier;
+        let cases = [
+            (false, identifier, true),
+            (false, identifier ^ 1, false),
+            (true, identifier, false),
+            (true, identifier ^ 1, false),
+        ];
+        let extendable = Slip39::recover(&reencode(true, identifier), b"TREZOR").unwrap();
+        for (flag, identifier, original) in cases {
+            let recovered = Slip39::recover(&reencode(flag, identifier), b"TREZOR").unwrap();
+            assert_eq!(recovered == master_secret, original);
+            assert_eq!(recovered == extendable, flag);
+        }
+        // The extendable checksum is not the original one.
+        let mut words: Vec<&str> = pair[0].split(' ').collect();
+        let extendable_mnemonic = reencode(true, identifier).remove(0);
+        let extendable_words: Vec<&str> = extendable_mnemonic.split(' ').collect();
+        words[..HEADER_LENGTH_WORDS].copy_from_slice(&extendable_words[..HEADER_LENGTH_WORDS]);
+        assert_eq!(
+            Slip39::recover(&[words.join(" ")], b"TREZOR"),
+            Err(Slip39Error::InvalidChecksum)
+        );
+    }
+
+    #[test]
+    fn test_integration() {
+        let master_secret = random_bytes(32);
+        for &extendable in &[false, true] {
+            let slip39 = Slip39 {
+                group_threshold: 2,
+                groups: vec![(1, 1), (2, 3), (3, 5)],
+                iteration_exponent: 0,
+                extendable,
+            };
+            let groups = slip39.split(&master_secret, b"passphrase").unwrap();
+            assert!(groups.iter().flatten().all(|m| m.split(' ').count() == 33));
+
+            let mnemonics = vec![
+                groups[1][2].clone(),
+                groups[2][4].clone(),
+                groups[2][0].clone(),
+                groups[1][0].clone(),
+                groups[2][1].clone(),
+            ];
+            let recovered = Slip39::recover(&mnemonics, b"passphrase");
+            assert_eq!(recovered, Ok(master_secret.clone()));
+            assert_eq!(
+                Slip39::recover(&mnemonics[..4], b"passphrase"),
+                Err(Slip39Error::InsufficientShares)
+            );
+            assert_ne!(Slip39::recover(&mnemonics, b""), Ok(master_secret.clone()));
+
+            // More complete groups than the group threshold are accepted.
+            let mut all_groups = mnemonics.clone();
+            all_groups.push(groups[0][0].clone());
+            assert_eq!(
+                Slip39::recover(&all_groups, b"passphrase"),
+                Ok(master_secret.clone())
+            );
+            // Incomplete groups are skipped.
+            let incomplete = [
+                groups[2][3].clone(),
+                groups[1][1].clone(),
+                groups[0][0].clone(),
+                groups[1][2].clone(),
+            ];
+            assert_eq!(
+                Slip39::recover(&incomplete, b"passphrase"),
+                Ok(master_secret.clone())
+            );
+        }
+    }
+
+    #[test]
+    fn test_group_index_out_of_range() {
+        let slip39 = Slip39 {
+            group_threshold: 1,
+            groups: vec![(1, 1), (1, 1)],
+            iteration_exponent: 0,
+            extendable: true,
+        };
+        let groups = slip39.split(&random_bytes(16), b"").unwrap();
+        let mut share = Share::from_mnemonic(&groups[1][0]).unwrap();
+        share.group_index = share.group_count;
+        assert_eq!(
+            Slip39::recover(&[share.to_mnemonic()], b""),
+            Err(Slip39Error::InconsistentShares(
+                "the group index exceeds the number of groups"
+            ))
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use crate::feldman_vss_bls12_381::VerifiableSecretSharingBls12381;
+use crate::lagrange::LagrangeCoefficients;
+use bls12_381::hash_to_curve::{ExpandMsgXmd, HashToCurve};
+use bls12_381::{pairing, G1Affine, G1Projective, G2Affine, G2Projective, Scalar};
+use sha2::Sha256;
+
+/// The domain separation tag of the IETF basic scheme, with public keys in G1
+/// and signatures in G2.
+pub const BLS_DST_BASIC: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+
+/// The domain separation tag of the IETF proof-of-possession scheme, with
+/// public keys in G1 and signatures in G2.
+pub const BLS_DST_POP: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// The `ThresholdBls` structure, for threshold BLS signatures with the
+/// shares of `VerifiableSecretSharingBls12381`.
+///
+/// Shareholders sign `H(m)` with their share, and any threshold of verified
+/// partial signatures aggregates, by Lagrange interpolation, into the
+/// signature of the secret under the public key `commitments[0]`, as defined
+/// by the IETF BLS signature draft for the ciphersuite of `dst`.
+pub struct ThresholdBls {
+    /// the threshold of shares.
+    pub threshold: usize,
+    /// the total number of shares.
+    pub share_amount: usize,
+    /// the ciphersuite's domain separation tag, e.g. `BLS_DST_BASIC`.
+    pub dst: &'static [u8],
+}
+
+impl ThresholdBls {
+    /// Hash a message to G2 with the ciphersuite's hash-to-curve.
+    pub fn hash_to_g2(&self, message: &[u8]) -> G2Projective {
+        <G2Projective as HashToCurve<ExpandMsgXmd<Sha256>>>::hash_to_curve(message, self.dst)
+    }
+
+    /// Sign a message with the whole secret key.
+    pub fn sign(&self, secret_key: &Scalar, message: &[u8]) -> G2Projective {
+        self.hash_to_g2(message) * secret_key
+    }
+
+    /// Compute a share's partial signature `H(m) * s_i`.
+    pub fn partial_sign(&self, share: (usize, Scalar), message: &[u8]) -> (usize, G2Projective) {
+        let (index, value) = share;
+        (index, self.hash_to_g2(message) * value)
+    }
+
+    /// Verify a partial signature against the share's verification share,
+    /// evaluated from the dealer's commitments. Identifiers outside
+    /// `1..=share_amount` are rejected.
+    pub fn verify_partial(
+        &self,
+        partial: (usize, G2Projective),
+        message: &[u8],
+        commitments: &[G1Projective],
+    ) -> bool {
+        let (index, signature) = partial;
+        if index == 0 || index > self.share_amount || commitments.len() != self.threshold {
+            return false;
+        }
+        let verification_share =
+            VerifiableSecretSharingBls12381::evaluate_commitments(commitments, index);
+        self.verify(&verification_share, message, &signature)
+    }
+
+    /// Aggregate threshold verified partial signatures into the signature of
+    /// the secret.
+    pub fn aggregate(&self, partials: &[(usize, G2Projective)]) -> G2Projective {
+        assert!(partials.len() == self.threshold);
+
+        let xs: Vec<usize> = partials.iter().map(|partial| partial.0).collect();
+        let coefficients = LagrangeCoefficients::new(&xs, Scalar::zero());
+        partials
+            .iter()
+            .zip(coefficients.coefficients())
+            .fold(G2Projective::identity(), |sum, (partial, coefficient)| {
+                sum + partial.1 * coefficient
+            })
+    }
+
+    /// Verify a signature, checking `e(g1, signature) == e(public_key, H(m))`.
+    pub fn verify(
+        &self,
+        public_key: &G1Projective,
+        message: &[u8],
+        signature: &G2Projective,
+    ) -> bool {
+        let public_key = G1Affine::from(public_key);
+        if bool::from(public_key.is_identity()) {
+            return false;
+        }
+        pairing(&G1Affine::generator(), &G2Affine::from(signature))
+            == pairing(&public_key, &G2Affine::from(self.hash_to_g2(message)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feldman_vss_bls12_381::new_random_bls12_381;
+
+    #[test]
+    fn test_integration() {
+        let secret = new_random_bls12_381();
+        let vss = VerifiableSecretSharingBls12381 {
+            threshold: 3,
+            share_amount: 5,
+        };
+        let (shares, commitments) = vss.split(&secret);
+        let bls = ThresholdBls {
+            threshold: 3,
+            share_amount: 5,
+            dst: BLS_DST_BASIC,
+        };
+        let message = b"block 42";
+        let partials: Vec<(usize, G2Projective)> = [shares[3], shares[0], shares[1]]
+            .iter()
+            .map(|&share| bls.partial_sign(share, message))
+            .collect();
+        assert!(partials
+            .iter()
+            .all(|&partial| bls.verify_partial(partial, message, &commitments)));
+        let signature = bls.aggregate(&partials);
+        assert_eq!(signature, bls.sign(&secret, message));
+        assert!(bls.verify(&commitments[0], message, &signature));
+        assert!(!bls.verify(&commitments[0], b"block 43", &signature));
+
+        let forged = (partials[0].0, partials[0].1 + G2Projective::generator());
+        assert!(!bls.verify_partial(forged, message, &commitments));
+        let outside = bls.partial_sign((6, shares[0].1), message);
+        assert!(!bls.verify_partial(outside, message, &commitments));
+    }
+
+    fn from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_hash_to_g2_vectors() {
+        // RFC 9380, appendix J.10.1: BLS12381G2_XMD:SHA-256_SSWU_RO_.
+        let bls = ThresholdBls {
+            threshold: 1,
+            share_amount: 1,
+            dst: b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_",
+        };
+        let vectors: [(&[u8], &str); 2] = [
+            (
+                b"",
+                "05cb8437535e20ecffaef7752baddf98034139c38452458baeefab379ba13dff5bf5dd71b72418717047f5b0f37da03d\
+                 0141ebfbdca40eb85b87142e130ab689c673cf60f1a3e98d69335266f30d9b8d4ac44c1038e9dcdd5393faf5c41fb78a\
+                 12424ac32561493f3fe3c260708a12b7c620e7be00099a974e259ddc7d1f6395c3c811cdd19f1e8dbf3e9ecfdcbab8d6\
+                 0503921d7f6a12805e72940b963c0cf3471c7b2a524950ca195d11062ee75ec076daf2d4bc358c4b190c0c98064fdd92",
+            ),
+            (
+                b"abc",
+                "139cddbccdc5e91b9623efd38c49f81a6f83f175e80b06fc374de9eb4b41dfe4ca3a230ed250fbe3a2acf73a41177fd8\
+                 02c2d18e033b960562aae3cab37a27ce00d80ccd5ba4b7fe0e7a210245129dbec7780ccc7954725f4168aff2787776e6\
+                 00aa65dae3c8d732d10ecd2c50f8a1baf3001578f71c694e03866e9f3d49ac1e1ce70dd94a733534f106d4cec0eddd16\
+                 1787327b68159716a37440985269cf584bcb1e621d3a7202be6ea05c4cfe244aeb197642555a0645fb87bf7466b2ba48",
+            ),
+        ];
+        for (message, expected) in vectors.iter() {
+            let point = G2Affine::from(bls.hash_to_g2(message));
+            assert_eq!(point.to_uncompressed().to_vec(), from_hex(expected));
+        }
+    }
+
+    #[test]
+    fn test_signature_vector() {
+        // The Ethereum consensus `bls/sign` vector for the zero message, under
+        // the proof-of-possession ciphersuite.
+        let bls = ThresholdBls {
+            threshold: 1,
+            share_amount: 1,
+            dst: BLS_DST_POP,
+        };
+        let mut secret_bytes = [0u8; 32];
+        secret_bytes.copy_from_slice(&from_hex(
+            "263dbd792f5b1be47ed85f8938c0f29586af0d3ac7b977f21c278fe1462040e3",
+        ));
+        secret_bytes.reverse();
+        let secret = Scalar::from_bytes(&secret_bytes).unwrap();
+        let public_key = G1Projective::generator() * secret;
+        assert_eq!(
+            G1Affine::from(public_key).to_compressed().to_vec(),
+            from_hex("a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20fd6e10c1b77654d067c0618f6e5a7f79a")
+        );
+
+        let message = [0u8; 32];
+        let signature = bls.sign(&secret, &message);
+        assert_eq!(
+            G2Affine::from(signature).to_compressed().to_vec(),
+            from_hex(
+                "b6ed936746e01f8ecf281f020953fbf1f01debd5657c4a383940b020b26507f6076334f91e2366c96e9ab279fb5158090352ea1c5b0c9274504f4f0e7053af24802e51e4568d164fe986834f41e55c8e850ce1f98458c0cfc9ab380b55285a55"
+            )
+        );
+        assert!(bls.verify(&public_key, &message, &signature));
+    }
+}
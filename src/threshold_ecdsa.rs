@@ -0,0 +1,521 @@
+use crate::lagrange::LagrangeCoefficients;
+use crate::paillier::{PaillierKeyPair, PaillierPublicKey};
+use crate::secp256k1_helper::{get_context, Secp256k1Point, Secp256k1Scalar};
+use num_bigint_dig::{BigUint, RandBigInt};
+use rand::{thread_rng, Rng};
+use secp256k1::{Message, PublicKey, Signature};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Errors raised by threshold ECDSA signing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThresholdEcdsaError {
+    /// A signer, or the sender or recipient of a message, that is not part of
+    /// the signing set.
+    UnknownSigner(usize),
+    /// A signer listed more than once in the signing set.
+    DuplicateSigner(usize),
+    /// No message, or several, from the given signer.
+    MissingMessage(usize),
+    /// The signer's revealed `Γ_i` does not match its first-round commitment.
+    InvalidCommitment(usize),
+    /// An MtA response from the signer is not a valid Paillier ciphertext.
+    InvalidCiphertext(usize),
+    /// The combined `δ` is zero or `R` is the point at infinity, so no
+    /// signature can be made with these nonces.
+    InvalidNonce,
+    /// A round was run before the previous one, or again after the nonce was
+    /// used.
+    UnexpectedRound,
+    /// The combined signature does not verify under the public key.
+    InvalidSignature,
+}
+
+impl fmt::Display for ThresholdEcdsaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThresholdEcdsaError::UnknownSigner(index) => write!(f, "unknown signer {}", index),
+            ThresholdEcdsaError::DuplicateSigner(index) => {
+                write!(f, "duplicated signer {}", index)
+            }
+            ThresholdEcdsaError::MissingMessage(index) => {
+                write!(f, "missing or duplicated message from signer {}", index)
+            }
+            ThresholdEcdsaError::InvalidCommitment(index) => {
+                write!(f, "invalid commitment from signer {}", index)
+            }
+            ThresholdEcdsaError::InvalidCiphertext(index) => {
+                write!(f, "invalid ciphertext from signer {}", index)
+            }
+            ThresholdEcdsaError::InvalidNonce => write!(f, "invalid combined nonce"),
+            ThresholdEcdsaError::UnexpectedRound => write!(f, "round run out of order"),
+            ThresholdEcdsaError::InvalidSignature => write!(f, "invalid signature"),
+        }
+    }
+}
+
+impl std::error::Error for ThresholdEcdsaError {}
+
+/// The first-round broadcast: the signer's Paillier key, its encrypted nonce
+/// share `k_i` and a commitment to `Γ_i = g * γ_i`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EcdsaRound1 {
+    /// the sender.
+    pub index: usize,
+    /// the sender's Paillier public key.
+    pub paillier_key: PaillierPublicKey,
+    /// `Enc_i(k_i)`.
+    pub encrypted_k: BigUint,
+    /// the hash commitment to `Γ_i`.
+    pub gamma_commitment: [u8; 32],
+}
+
+/// The second-round point-to-point message: the sender's halves of the
+/// multiplicative-to-additive conversions of `k_to * γ_from` and `k_to * w_from`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EcdsaMtaResponse {
+    /// the sender.
+    pub from: usize,
+    /// the recipient.
+    pub to: usize,
+    /// `Enc_to(k_to * γ_from + β')`.
+    pub gamma: BigUint,
+    /// `Enc_to(k_to * w_from + ν')`.
+    pub share: BigUint,
+}
+
+/// The third-round broadcast: the additive share `δ_i` of `k * γ`, and the
+/// opening of the commitment to `Γ_i`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EcdsaRound3 {
+    /// the sender.
+    pub index: usize,
+    /// the share `δ_i` of `k * γ`.
+    pub delta: Secp256k1Scalar,
+    /// `Γ_i = g * γ_i`.
+    pub gamma_point: Secp256k1Point,
+    /// the blinding of the commitment to `Γ_i`.
+    pub blinding: [u8; 32],
+}
+
+/// A signer's additive share `s_i = m * k_i + r * σ_i` of the signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EcdsaPartialSignature {
+    /// the sender.
+    pub index: usize,
+    /// the share of `s`.
+    pub s: Secp256k1Scalar,
+}
+
+/// A signer of the threshold ECDSA protocol over secp256k1.
+///
+/// It follows Gennaro and Goldfeder's construction: every signer holds
+/// additive shares `k_i`, `γ_i` and `w_i = λ_i x_i` (the Lagrange-weighted
+/// share of `VerifiableSecretSharing::split`, or of a DKG), and the pairwise
+/// products are converted into additive shares of `k γ` and `k x` with
+/// Paillier-based MtA. The signature `(r, s)` uses the nonce `R = g * k^-1`
+/// and `s = k (m + r x)`.
+///
+/// The MtA messages carry neither range proofs nor proofs that the Paillier
+/// moduli are well formed, so the protocol is insecure against malicious
+/// signers: a signer deviating from it can extract the other signers' key
+/// shares. It only protects against honest-but-curious signers, which is why
+/// the module is behind the `insecure-threshold-ecdsa` feature.
+///
+/// Each signer signs a single message: `round4` consumes the nonce shares,
+/// and a signer must be created anew for every signature.
+pub struct ThresholdEcdsaSigner {
+    index: usize,
+    signers: Vec<usize>,
+    w: Secp256k1Scalar,
+    paillier: PaillierKeyPair,
+    nonces: Option<(Secp256k1Scalar, Secp256k1Scalar)>,
+    blinding: [u8; 32],
+    commitments: BTreeMap<usize, [u8; 32]>,
+    mta_masks: Option<(Secp256k1Scalar, Secp256k1Scalar)>,
+    sigma: Option<Secp256k1Scalar>,
+    r: Option<Secp256k1Scalar>,
+}
+
+impl ThresholdEcdsaSigner {
+    /// Prepare signer `share.0` of the signing set `signers` (at least the
+    /// threshold of share identifiers), with its long-term Paillier key.
+    pub fn new(
+        share: (usize, Secp256k1Scalar),
+        signers: &[usize],
+        paillier: PaillierKeyPair,
+    ) -> Result<Self, ThresholdEcdsaError> {
+        let (index, value) = share;
+        let mut sorted = signers.to_vec();
+        sorted.sort_unstable();
+        if let Some(pair) = sorted.windows(2).find(|pair| pair[0] == pair[1]) {
+            return Err(ThresholdEcdsaError::DuplicateSigner(pair[0]));
+        }
+        if !signers.contains(&index) {
+            return Err(ThresholdEcdsaError::UnknownSigner(index));
+        }
+        let lambda = LagrangeCoefficients::new(signers, Secp256k1Scalar::zero())
+            .coefficient(index)
+            .ok_or(ThresholdEcdsaError::UnknownSigner(index))?;
+        let mut blinding = [0u8; 32];
+        thread_rng().fill(&mut blinding[..]);
+        Ok(ThresholdEcdsaSigner {
+            index,
+            signers: signers.to_vec(),
+            w: lambda * value,
+            paillier,
+            nonces: Some((Secp256k1Scalar::new_random(), Secp256k1Scalar::new_random())),
+            blinding,
+            commitments: BTreeMap::new(),
+            mta_masks: None,
+            sigma: None,
+            r: None,
+        })
+    }
+
+    /// Round 1: broadcast `Enc_i(k_i)` and the commitment to `Γ_i`.
+    pub fn round1(&self) -> Result<EcdsaRound1, ThresholdEcdsaError> {
+        let (k, gamma) = self.nonces.ok_or(ThresholdEcdsaError::UnexpectedRound)?;
+        let gamma_point = Secp256k1Point::generator() * gamma;
+        Ok(EcdsaRound1 {
+            index: self.index,
+            paillier_key: self.paillier.public_key.clone(),
+            encrypted_k: self.paillier.public_key.encrypt(&to_biguint(&k)),
+            gamma_commitment: commit(self.index, &gamma_point, &self.blinding),
+        })
+    }
+
+    /// Round 2: answer every other signer's encrypted nonce share with the
+    /// MtA messages of `k_j * γ_i` and `k_j * w_i`.
+    pub fn round2(
+        &mut self,
+        round1: &[EcdsaRound1],
+    ) -> Result<Vec<EcdsaMtaResponse>, ThresholdEcdsaError> {
+        let (_, gamma_i) = self.nonces.ok_or(ThresholdEcdsaError::UnexpectedRound)?;
+        let messages = self.by_signer(round1, |message| message.index)?;
+        let mut beta_sum = Secp256k1Scalar::zero();
+        let mut nu_sum = Secp256k1Scalar::zero();
+        let mut responses = Vec::new();
+        for (&j, message) in &messages {
+            self.commitments.insert(j, message.gamma_commitment);
+            if j == self.index {
+                continue;
+            }
+            let (gamma, beta) = mta_respond(&message.paillier_key, &message.encrypted_k, &gamma_i);
+            let (share, nu) = mta_respond(&message.paillier_key, &message.encrypted_k, &self.w);
+            beta_sum = beta_sum + beta;
+            nu_sum = nu_sum + nu;
+            responses.push(EcdsaMtaResponse {
+                from: self.index,
+                to: j,
+                gamma,
+                share,
+            });
+        }
+        self.mta_masks = Some((beta_sum, nu_sum));
+        Ok(responses)
+    }
+
+    /// Round 3: complete the MtA with the responses addressed to this signer,
+    /// and broadcast `δ_i` with the opening of `Γ_i`.
+    pub fn round3(
+        &mut self,
+        responses: &[EcdsaMtaResponse],
+    ) -> Result<EcdsaRound3, ThresholdEcdsaError> {
+        let (k, gamma) = self.nonces.ok_or(ThresholdEcdsaError::UnexpectedRound)?;
+        let (beta_sum, nu_sum) = self.mta_masks.ok_or(ThresholdEcdsaError::UnexpectedRound)?;
+        let mut received: Vec<&EcdsaMtaResponse> = Vec::new();
+        for response in responses
+            .iter()
+            .filter(|response| response.to == self.index)
+        {
+            if !self.signers.contains(&response.from) || response.from == self.index {
+                return Err(ThresholdEcdsaError::UnknownSigner(response.from));
+            }
+            received.push(response);
+        }
+        for &j in self.signers.iter().filter(|&&j| j != self.index) {
+            if received
+                .iter()
+                .filter(|response| response.from == j)
+                .count()
+                != 1
+            {
+                return Err(ThresholdEcdsaError::MissingMessage(j));
+            }
+        }
+
+        let mut delta = k * gamma + beta_sum;
+        let mut sigma = k * self.w + nu_sum;
+        for response in received {
+            let decrypt = |ciphertext| {
+                self.paillier
+                    .decrypt(ciphertext)
+                    .map(|plaintext| from_biguint(&plaintext))
+                    .ok_or(ThresholdEcdsaError::InvalidCiphertext(response.from))
+            };
+            delta = delta + decrypt(&response.gamma)?;
+            sigma = sigma + decrypt(&response.share)?;
+        }
+        self.sigma = Some(sigma);
+        Ok(EcdsaRound3 {
+            index: self.index,
+            delta,
+            gamma_point: Secp256k1Point::generator() * gamma,
+            blinding: self.blinding,
+        })
+    }
+
+    /// Round 4: check the openings, compute `R = (sum Γ_j) * δ^-1` and return
+    /// this signer's share of `s` for the 32-byte message hash.
+    ///
+    /// Signing two messages with the same nonce reveals the key, so the nonce
+    /// shares are erased and any further round fails with `UnexpectedRound`.
+    pub fn round4(
+        &mut self,
+        round3: &[EcdsaRound3],
+        message_hash: &[u8; 32],
+    ) -> Result<EcdsaPartialSignature, ThresholdEcdsaError> {
+        let sigma = self.sigma.ok_or(ThresholdEcdsaError::UnexpectedRound)?;
+        let (k, _) = self.nonces.ok_or(ThresholdEcdsaError::UnexpectedRound)?;
+        let messages = self.by_signer(round3, |message| message.index)?;
+        let mut delta = Secp256k1Scalar::zero();
+        let mut gamma_sum: Option<Secp256k1Point> = None;
+        for (&j, message) in &messages {
+            if commit(j, &message.gamma_point, &message.blinding) != self.commitments[&j] {
+                return Err(ThresholdEcdsaError::InvalidCommitment(j));
+            }
+            delta = delta + message.delta;
+            gamma_sum = match gamma_sum {
+                Some(sum) => Some(
+                    sum.checked_add(&message.gamma_point)
+                        .ok_or(ThresholdEcdsaError::InvalidNonce)?,
+                ),
+                None => Some(message.gamma_point),
+            };
+        }
+        if delta == Secp256k1Scalar::zero() {
+            return Err(ThresholdEcdsaError::InvalidNonce);
+        }
+        let big_r = gamma_sum
+            .and_then(|sum| sum.checked_mul(&delta.inv()))
+            .ok_or(ThresholdEcdsaError::InvalidNonce)?;
+        let r = Secp256k1Scalar::from_bytes_mod_order(&big_r.serialize()[1..]);
+        self.nonces = None;
+        self.sigma = None;
+        self.r = Some(r);
+        let m = Secp256k1Scalar::from_bytes_mod_order(message_hash);
+        Ok(EcdsaPartialSignature {
+            index: self.index,
+            s: m * k + r * sigma,
+        })
+    }
+
+    /// Combine the partial signatures of all signers into a low-S ECDSA
+    /// signature, verified under `public_key` (`commitments[0]`).
+    pub fn combine(
+        &self,
+        partials: &[EcdsaPartialSignature],
+        public_key: &Secp256k1Point,
+        message_hash: &[u8; 32],
+    ) -> Result<Signature, ThresholdEcdsaError> {
+        let r = self.r.ok_or(ThresholdEcdsaError::UnexpectedRound)?;
+        let messages = self.by_signer(partials, |partial| partial.index)?;
+        let s = messages
+            .values()
+            .fold(Secp256k1Scalar::zero(), |sum, partial| sum + partial.s);
+
+        let mut compact = [0u8; 64];
+        compact[..32].copy_from_slice(&r.to_bytes());
+        compact[32..].copy_from_slice(&s.to_bytes());
+        let mut signature =
+            Signature::from_compact(&compact).map_err(|_| ThresholdEcdsaError::InvalidSignature)?;
+        signature.normalize_s();
+        let message = Message::from_slice(message_hash).unwrap();
+        let public_key = PublicKey::from_slice(&public_key.serialize()).unwrap();
+        get_context()
+            .verify(&message, &signature, &public_key)
+            .map_err(|_| ThresholdEcdsaError::InvalidSignature)?;
+        Ok(signature)
+    }
+
+    /// Index the messages by sender, checking that every signer sent exactly
+    /// one of them.
+    fn by_signer<'a, T>(
+        &self,
+        messages: &'a [T],
+        sender: impl Fn(&T) -> usize,
+    ) -> Result<BTreeMap<usize, &'a T>, ThresholdEcdsaError> {
+        let mut indexed = BTreeMap::new();
+        for message in messages {
+            let index = sender(message);
+            if !self.signers.contains(&index) {
+                return Err(ThresholdEcdsaError::UnknownSigner(index));
+            }
+            if indexed.insert(index, message).is_some() {
+                return Err(ThresholdEcdsaError::MissingMessage(index));
+            }
+        }
+        match self.signers.iter().find(|j| !indexed.contains_key(j)) {
+            Some(&j) => Err(ThresholdEcdsaError::MissingMessage(j)),
+            None => Ok(indexed),
+        }
+    }
+}
+
+/// Bob's side of MtA: from `Enc(a)`, return `Enc(a * b + β')` and Bob's
+/// additive share `-β'`. `β'` is sampled below `n / 2`, far above `a * b`,
+/// so the sum neither wraps nor reveals `a * b`.
+fn mta_respond(
+    key: &PaillierPublicKey,
+    encrypted_a: &BigUint,
+    b: &Secp256k1Scalar,
+) -> (BigUint, Secp256k1Scalar) {
+    let beta_prime = thread_rng().gen_biguint_below(&(&key.n >> 1));
+    let response = key.add(
+        &key.mul(encrypted_a, &to_biguint(b)),
+        &key.encrypt(&beta_prime),
+    );
+    (
+        response,
+        Secp256k1Scalar::zero() - from_biguint(&beta_prime),
+    )
+}
+
+fn commit(index: usize, gamma_point: &Secp256k1Point, blinding: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"secret-sharing-extra threshold ecdsa gamma");
+    hasher.update((index as u64).to_be_bytes());
+    hasher.update(gamma_point.serialize());
+    hasher.update(blinding);
+    let mut commitment = [0u8; 32];
+    commitment.copy_from_slice(&hasher.finalize());
+    commitment
+}
+
+fn to_biguint(scalar: &Secp256k1Scalar) -> BigUint {
+    BigUint::from_bytes_be(&scalar.to_bytes())
+}
+
+fn from_biguint(n: &BigUint) -> Secp256k1Scalar {
+    Secp256k1Scalar::from_bytes_mod_order(&n.to_bytes_be())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feldman_vss_secp256k1::VerifiableSecretSharing;
+
+    #[test]
+    fn test_integration() {
+        let secret = Secp256k1Scalar::new_random();
+        let vss = VerifiableSecretSharing {
+            threshold: 3,
+            share_amount: 5,
+        };
+        let (shares, commitments) = vss.split(&secret);
+        let signers = [2, 4, 5];
+        let message_hash: [u8; 32] = Sha256::digest(b"threshold ecdsa").into();
+
+        let mut parties: Vec<ThresholdEcdsaSigner> = signers
+            .iter()
+            .map(|&i| {
+                ThresholdEcdsaSigner::new(shares[i - 1], &signers, PaillierKeyPair::generate(1024))
+                    .unwrap()
+            })
+            .collect();
+        let round1: Vec<EcdsaRound1> = parties
+            .iter()
+            .map(|party| party.round1().unwrap())
+            .collect();
+        let responses: Vec<EcdsaMtaResponse> = parties
+            .iter_mut()
+            .flat_map(|party| party.round2(&round1).unwrap())
+            .collect();
+        let round3: Vec<EcdsaRound3> = parties
+            .iter_mut()
+            .map(|party| party.round3(&responses).unwrap())
+            .collect();
+        let partials: Vec<EcdsaPartialSignature> = parties
+            .iter_mut()
+            .map(|party| party.round4(&round3, &message_hash).unwrap())
+            .collect();
+        let signature = parties[0]
+            .combine(&partials, &commitments[0], &message_hash)
+            .unwrap();
+
+        // The nonce shares are gone once used.
+        let other_hash: [u8; 32] = Sha256::digest(b"other message").into();
+        assert_eq!(
+            parties[0].round4(&round3, &other_hash),
+            Err(ThresholdEcdsaError::UnexpectedRound)
+        );
+        assert_eq!(
+            parties[0].round1(),
+            Err(ThresholdEcdsaError::UnexpectedRound)
+        );
+
+        let public_key = PublicKey::from_slice(&commitments[0].serialize()).unwrap();
+        let message = Message::from_slice(&message_hash).unwrap();
+        assert!(secp256k1::Secp256k1::verification_only()
+            .verify(&message, &signature, &public_key)
+            .is_ok());
+
+        let mut forged = partials.clone();
+        forged[1].s = forged[1].s + Secp256k1Scalar::one();
+        assert_eq!(
+            parties[0].combine(&forged, &commitments[0], &message_hash),
+            Err(ThresholdEcdsaError::InvalidSignature)
+        );
+        assert_eq!(
+            parties[1].combine(&partials[1..], &commitments[0], &message_hash),
+            Err(ThresholdEcdsaError::MissingMessage(2))
+        );
+    }
+
+    #[test]
+    fn test_malicious_inputs() {
+        let vss = VerifiableSecretSharing {
+            threshold: 2,
+            share_amount: 3,
+        };
+        let (shares, _) = vss.split(&Secp256k1Scalar::new_random());
+        let paillier = PaillierKeyPair::generate(1024);
+        assert_eq!(
+            ThresholdEcdsaSigner::new(shares[0], &[1, 2, 1], paillier.clone()).err(),
+            Some(ThresholdEcdsaError::DuplicateSigner(1))
+        );
+        assert_eq!(
+            ThresholdEcdsaSigner::new(shares[0], &[], paillier).err(),
+            Some(ThresholdEcdsaError::UnknownSigner(1))
+        );
+
+        // The last signer picks its `δ_i` to cancel the others.
+        let signers = [1, 3];
+        let mut parties: Vec<ThresholdEcdsaSigner> = signers
+            .iter()
+            .map(|&i| {
+                ThresholdEcdsaSigner::new(shares[i - 1], &signers, PaillierKeyPair::generate(1024))
+                    .unwrap()
+            })
+            .collect();
+        let round1: Vec<EcdsaRound1> = parties
+            .iter()
+            .map(|party| party.round1().unwrap())
+            .collect();
+        let responses: Vec<EcdsaMtaResponse> = parties
+            .iter_mut()
+            .flat_map(|party| party.round2(&round1).unwrap())
+            .collect();
+        let mut round3: Vec<EcdsaRound3> = parties
+            .iter_mut()
+            .map(|party| party.round3(&responses).unwrap())
+            .collect();
+        round3[1].delta = Secp256k1Scalar::zero() - round3[0].delta;
+        assert_eq!(
+            parties[0].round4(&round3, &[1u8; 32]),
+            Err(ThresholdEcdsaError::InvalidNonce)
+        );
+    }
+}
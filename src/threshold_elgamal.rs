@@ -0,0 +1,207 @@
+use crate::feldman_vss::{new_random, VerifiableSecretSharingRistretto};
+use crate::lagrange::LagrangeCoefficients;
+use crate::proofs::DleqProof;
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+use sha2::{Digest, Sha512};
+
+/// An ElGamal ciphertext `(g * r, message + public_key * r)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElGamalCiphertext {
+    /// the ephemeral element `g * r`.
+    pub c1: RistrettoPoint,
+    /// the masked message `message + public_key * r`.
+    pub c2: RistrettoPoint,
+}
+
+/// A shareholder's partial decryption `c1 * s_i`, with the proof that it uses
+/// the same share as the verification share `g * s_i`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PartialDecryption {
+    /// the share index.
+    pub index: usize,
+    /// the partial decryption `c1 * s_i`.
+    pub value: RistrettoPoint,
+    /// the DLEQ proof that `log_g (g * s_i) == log_{c1} value`.
+    pub proof: DleqProof<Scalar, RistrettoPoint>,
+}
+
+/// The `ThresholdElGamalRistretto` structure, for threshold ElGamal
+/// decryption with the shares of `VerifiableSecretSharingRistretto`.
+///
+/// The public key is `commitments[0]`, and any threshold of verified partial
+/// decryptions can be combined, by Lagrange interpolation in the exponent,
+/// into `c1 * secret`.
+pub struct ThresholdElGamalRistretto {
+    /// the threshold of shares.
+    pub threshold: usize,
+    /// the total number of shares.
+    pub share_amount: usize,
+}
+
+impl ThresholdElGamalRistretto {
+    /// Encrypt a message, encoded as a group element, under the public key.
+    pub fn encrypt(public_key: &RistrettoPoint, message: &RistrettoPoint) -> ElGamalCiphertext {
+        let r = new_random();
+        ElGamalCiphertext {
+            c1: RISTRETTO_BASEPOINT_POINT * r,
+            c2: message + public_key * r,
+        }
+    }
+
+    /// Encapsulate a fresh 32-byte key under the public key, for hybrid
+    /// encryption of arbitrary payloads. Returns the ephemeral element `c1`,
+    /// to be decrypted with `combine`, and the key.
+    pub fn encapsulate(public_key: &RistrettoPoint) -> (RistrettoPoint, [u8; 32]) {
+        let r = new_random();
+        let c1 = RISTRETTO_BASEPOINT_POINT * r;
+        (c1, Self::derive_key(&c1, &(public_key * r)))
+    }
+
+    /// Derive the key encapsulated in `c1`, from the combined `c1 * secret`.
+    pub fn derive_key(c1: &RistrettoPoint, shared: &RistrettoPoint) -> [u8; 32] {
+        let mut hasher = Sha512::new();
+        hasher.update(b"secret-sharing-extra threshold elgamal kem");
+        hasher.update(c1.compress().as_bytes());
+        hasher.update(shared.compress().as_bytes());
+        let mut key = [0u8; 32];
+        key.copy_from_slice(&hasher.finalize()[..32]);
+        key
+    }
+
+    /// The verification share `g * s_i` of the share at `index`.
+    pub fn verification_share(commitments: &[RistrettoPoint], index: usize) -> RistrettoPoint {
+        VerifiableSecretSharingRistretto::evaluate_commitments(commitments, index)
+    }
+
+    /// Compute the partial decryption of `c1` with a share.
+    pub fn partial_decrypt(c1: &RistrettoPoint, share: (usize, Scalar)) -> PartialDecryption {
+        let (index, value) = share;
+        let generator = RISTRETTO_BASEPOINT_POINT;
+        let partial = c1 * value;
+        let proof = DleqProof::prove(
+            &mut Self::transcript(index),
+            &generator,
+            &(generator * value),
+            c1,
+            &partial,
+            &value,
+        );
+        PartialDecryption {
+            index,
+            value: partial,
+            proof,
+        }
+    }
+
+    /// Verify a partial decryption of `c1` against the dealer's commitments.
+    /// Returns false if there are no commitments.
+    pub fn verify_partial(
+        c1: &RistrettoPoint,
+        partial: &PartialDecryption,
+        commitments: &[RistrettoPoint],
+    ) -> bool {
+        !commitments.is_empty()
+            && partial.proof.verify(
+                &mut Self::transcript(partial.index),
+                &RISTRETTO_BASEPOINT_POINT,
+                &Self::verification_share(commitments, partial.index),
+                c1,
+                &partial.value,
+            )
+    }
+
+    /// Combine threshold verified partial decryptions into `c1 * secret`.
+    pub fn combine(&self, partials: &[PartialDecryption]) -> RistrettoPoint {
+        assert!(partials.len() == self.threshold);
+
+        let xs: Vec<usize> = partials.iter().map(|partial| partial.index).collect();
+        let coefficients = LagrangeCoefficients::new(&xs, Scalar::zero());
+        partials
+            .iter()
+            .zip(coefficients.coefficients())
+            .map(|(partial, coefficient)| partial.value * coefficient)
+            .sum()
+    }
+
+    /// Decrypt a ciphertext with threshold verified partial decryptions of its
+    /// `c1`.
+    pub fn decrypt(
+        &self,
+        ciphertext: &ElGamalCiphertext,
+        partials: &[PartialDecryption],
+    ) -> RistrettoPoint {
+        ciphertext.c2 - self.combine(partials)
+    }
+
+    fn transcript(index: usize) -> Transcript {
+        let mut transcript = Transcript::new(b"secret-sharing-extra threshold elgamal");
+        transcript.append_u64(b"index", index as u64);
+        transcript
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integration() {
+        let secret = new_random();
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: 3,
+            share_amount: 5,
+        };
+        let (shares, commitments) = vss.split(&secret);
+        let elgamal = ThresholdElGamalRistretto {
+            threshold: 3,
+            share_amount: 5,
+        };
+
+        let message = RISTRETTO_BASEPOINT_POINT * new_random();
+        let ciphertext = ThresholdElGamalRistretto::encrypt(&commitments[0], &message);
+        let partials: Vec<PartialDecryption> = [shares[4], shares[0], shares[2]]
+            .iter()
+            .map(|&share| ThresholdElGamalRistretto::partial_decrypt(&ciphertext.c1, share))
+            .collect();
+        assert!(partials.iter().all(|partial| {
+            ThresholdElGamalRistretto::verify_partial(&ciphertext.c1, partial, &commitments)
+        }));
+        assert_eq!(elgamal.decrypt(&ciphertext, &partials), message);
+
+        let mut forged = partials[1];
+        forged.value += RISTRETTO_BASEPOINT_POINT;
+        assert!(!ThresholdElGamalRistretto::verify_partial(
+            &ciphertext.c1,
+            &forged,
+            &commitments
+        ));
+        assert!(!ThresholdElGamalRistretto::verify_partial(
+            &ciphertext.c1,
+            &partials[0],
+            &[]
+        ));
+    }
+
+    #[test]
+    fn test_encapsulate() {
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: 2,
+            share_amount: 3,
+        };
+        let (shares, commitments) = vss.split(&new_random());
+        let elgamal = ThresholdElGamalRistretto {
+            threshold: 2,
+            share_amount: 3,
+        };
+        let (c1, key) = ThresholdElGamalRistretto::encapsulate(&commitments[0]);
+        let partials: Vec<PartialDecryption> = shares[1..]
+            .iter()
+            .map(|&share| ThresholdElGamalRistretto::partial_decrypt(&c1, share))
+            .collect();
+        let shared = elgamal.combine(&partials);
+        assert_eq!(ThresholdElGamalRistretto::derive_key(&c1, &shared), key);
+    }
+}
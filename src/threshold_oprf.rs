@@ -0,0 +1,259 @@
+use crate::feldman_vss::{new_random, VerifiableSecretSharingRistretto};
+use crate::lagrange::LagrangeCoefficients;
+use crate::proofs::DleqProof;
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+use sha2::{Digest, Sha512};
+
+/// The RFC 9497 context string of the ristretto255-SHA512 suite in OPRF mode.
+pub const OPRF_CONTEXT: &[u8] = b"OPRFV1-\x00-ristretto255-SHA512";
+
+/// A shareholder's evaluation `blinded * s_i`, with the proof that it uses the
+/// same share as the verification share `g * s_i`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OprfEvaluation {
+    /// the share index.
+    pub index: usize,
+    /// the evaluation `blinded * s_i`.
+    pub value: RistrettoPoint,
+    /// the DLEQ proof that `log_g (g * s_i) == log_blinded value`.
+    pub proof: DleqProof<Scalar, RistrettoPoint>,
+}
+
+/// The `ThresholdOprfRistretto` structure, for threshold evaluation of the
+/// RFC 9497 ristretto255-SHA512 OPRF with the shares of
+/// `VerifiableSecretSharingRistretto`.
+///
+/// The client blinds `H(m)`, any threshold of verified evaluations combine
+/// into `blinded * s`, and `finalize` gives the same output as a single
+/// server holding the key `s`. Used with the blind `Scalar::one()`, this is a
+/// threshold VRF, e.g. for a randomness beacon.
+pub struct ThresholdOprfRistretto {
+    /// the threshold of shares.
+    pub threshold: usize,
+    /// the total number of shares.
+    pub share_amount: usize,
+}
+
+impl ThresholdOprfRistretto {
+    /// Deterministically derive a key pair from a seed, as RFC 9497
+    /// `DeriveKeyPair` does.
+    pub fn derive_key_pair(seed: &[u8; 32], info: &[u8]) -> (Scalar, RistrettoPoint) {
+        let mut derive_input = seed.to_vec();
+        derive_input.extend_from_slice(&(info.len() as u16).to_be_bytes());
+        derive_input.extend_from_slice(info);
+        let dst = [b"DeriveKeyPair".as_ref(), OPRF_CONTEXT].concat();
+        for counter in 0..=255u8 {
+            let mut input = derive_input.clone();
+            input.push(counter);
+            let secret_key = Scalar::from_bytes_mod_order_wide(&expand_message_xmd(&input, &dst));
+            if secret_key != Scalar::zero() {
+                return (secret_key, RISTRETTO_BASEPOINT_POINT * secret_key);
+            }
+        }
+        panic!("DeriveKeyPairError");
+    }
+
+    /// Hash an input to the group, with the suite's `HashToGroup`.
+    pub fn hash_to_group(input: &[u8]) -> RistrettoPoint {
+        let dst = [b"HashToGroup-".as_ref(), OPRF_CONTEXT].concat();
+        RistrettoPoint::from_uniform_bytes(&expand_message_xmd(input, &dst))
+    }
+
+    /// Blind an input, returning the blind and the blinded element to send to
+    /// the shareholders.
+    pub fn blind(input: &[u8]) -> (Scalar, RistrettoPoint) {
+        let blind = new_random();
+        (blind, Self::hash_to_group(input) * blind)
+    }
+
+    /// Evaluate a blinded element with a share.
+    pub fn evaluate(share: (usize, Scalar), blinded: &RistrettoPoint) -> OprfEvaluation {
+        let (index, value) = share;
+        let generator = RISTRETTO_BASEPOINT_POINT;
+        let evaluation = blinded * value;
+        let proof = DleqProof::prove(
+            &mut Self::transcript(index),
+            &generator,
+            &(generator * value),
+            blinded,
+            &evaluation,
+            &value,
+        );
+        OprfEvaluation {
+            index,
+            value: evaluation,
+            proof,
+        }
+    }
+
+    /// Verify an evaluation of `blinded` against the dealer's commitments.
+    /// Returns false if there are no commitments.
+    pub fn verify_evaluation(
+        blinded: &RistrettoPoint,
+        evaluation: &OprfEvaluation,
+        commitments: &[RistrettoPoint],
+    ) -> bool {
+        !commitments.is_empty()
+            && evaluation.proof.verify(
+                &mut Self::transcript(evaluation.index),
+                &RISTRETTO_BASEPOINT_POINT,
+                &VerifiableSecretSharingRistretto::evaluate_commitments(
+                    commitments,
+                    evaluation.index,
+                ),
+                blinded,
+                &evaluation.value,
+            )
+    }
+
+    /// Combine threshold verified evaluations into `blinded * s`.
+    pub fn combine(&self, evaluations: &[OprfEvaluation]) -> RistrettoPoint {
+        assert!(evaluations.len() == self.threshold);
+
+        let xs: Vec<usize> = evaluations
+            .iter()
+            .map(|evaluation| evaluation.index)
+            .collect();
+        let coefficients = LagrangeCoefficients::new(&xs, Scalar::zero());
+        evaluations
+            .iter()
+            .zip(coefficients.coefficients())
+            .map(|(evaluation, coefficient)| evaluation.value * coefficient)
+            .sum()
+    }
+
+    /// Unblind the combined evaluation and compute the 64-byte OPRF output.
+    pub fn finalize(input: &[u8], blind: &Scalar, evaluated: &RistrettoPoint) -> [u8; 64] {
+        let unblinded = (evaluated * blind.invert()).compress();
+        let mut hasher = Sha512::new();
+        hasher.update((input.len() as u16).to_be_bytes());
+        hasher.update(input);
+        hasher.update(32u16.to_be_bytes());
+        hasher.update(unblinded.as_bytes());
+        hasher.update(b"Finalize");
+        let mut output = [0u8; 64];
+        output.copy_from_slice(&hasher.finalize());
+        output
+    }
+
+    fn transcript(index: usize) -> Transcript {
+        let mut transcript = Transcript::new(b"secret-sharing-extra threshold oprf");
+        transcript.append_u64(b"index", index as u64);
+        transcript
+    }
+}
+
+/// `expand_message_xmd` of RFC 9380 with SHA-512, for 64 output bytes.
+fn expand_message_xmd(message: &[u8], dst: &[u8]) -> [u8; 64] {
+    let dst_prime = [dst, &[dst.len() as u8]].concat();
+    let b0 = Sha512::new()
+        .chain([0u8; 128])
+        .chain(message)
+        .chain(64u16.to_be_bytes())
+        .chain([0u8])
+        .chain(&dst_prime)
+        .finalize();
+    let b1 = Sha512::new()
+        .chain(b0)
+        .chain([1u8])
+        .chain(&dst_prime)
+        .finalize();
+    let mut uniform_bytes = [0u8; 64];
+    uniform_bytes.copy_from_slice(&b1);
+    uniform_bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_rfc9497_vector() {
+        let (secret_key, _) = ThresholdOprfRistretto::derive_key_pair(&[0xa3; 32], b"test key");
+        assert_eq!(
+            secret_key.as_bytes().to_vec(),
+            hex("5ebcea5ee37023ccb9fc2d2019f9d7737be85591ae8652ffa9ef0f4d37063b0e")
+        );
+
+        // The output does not depend on the blind.
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: 2,
+            share_amount: 3,
+        };
+        let (shares, _) = vss.split(&secret_key);
+        let oprf = ThresholdOprfRistretto {
+            threshold: 2,
+            share_amount: 3,
+        };
+        let (blind, blinded) = ThresholdOprfRistretto::blind(&[0x00]);
+        let evaluations: Vec<OprfEvaluation> = shares[1..]
+            .iter()
+            .map(|&share| ThresholdOprfRistretto::evaluate(share, &blinded))
+            .collect();
+        let output = ThresholdOprfRistretto::finalize(&[0x00], &blind, &oprf.combine(&evaluations));
+        assert_eq!(
+            output.to_vec(),
+            hex(concat!(
+                "527759c3d9366f277d8c6020418d96bb393ba2afb20ff90df23fb7708264e2f3",
+                "ab9135e3bd69955851de4b1f9fe8a0973396719b7912ba9ee8aa7d0b5e24bcf6"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_integration() {
+        let secret = new_random();
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: 3,
+            share_amount: 5,
+        };
+        let (shares, commitments) = vss.split(&secret);
+        let oprf = ThresholdOprfRistretto {
+            threshold: 3,
+            share_amount: 5,
+        };
+
+        let input = b"correct horse battery staple";
+        let (blind, blinded) = ThresholdOprfRistretto::blind(input);
+        let evaluations: Vec<OprfEvaluation> = [shares[2], shares[4], shares[0]]
+            .iter()
+            .map(|&share| ThresholdOprfRistretto::evaluate(share, &blinded))
+            .collect();
+        assert!(evaluations.iter().all(|evaluation| {
+            ThresholdOprfRistretto::verify_evaluation(&blinded, evaluation, &commitments)
+        }));
+        let combined = oprf.combine(&evaluations);
+        assert_eq!(combined, blinded * secret);
+        assert_eq!(
+            ThresholdOprfRistretto::finalize(input, &blind, &combined),
+            ThresholdOprfRistretto::finalize(
+                input,
+                &Scalar::one(),
+                &(ThresholdOprfRistretto::hash_to_group(input) * secret)
+            )
+        );
+
+        let mut forged = evaluations[0];
+        forged.value += RISTRETTO_BASEPOINT_POINT;
+        assert!(!ThresholdOprfRistretto::verify_evaluation(
+            &blinded,
+            &forged,
+            &commitments
+        ));
+        assert!(!ThresholdOprfRistretto::verify_evaluation(
+            &blinded,
+            &evaluations[0],
+            &[]
+        ));
+    }
+}
@@ -0,0 +1,131 @@
+use crate::feldman_vss::VerifiableSecretSharingRistretto;
+use crate::lagrange::LagrangeCoefficients;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::scalar::Scalar;
+use std::collections::BTreeSet;
+
+/// The shares held by a single participant of a weighted sharing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShareBundle {
+    /// the participant, numbered from 1.
+    pub participant: usize,
+    /// one share per unit of weight, with distinct identifiers.
+    pub shares: Vec<(usize, Scalar)>,
+}
+
+impl ShareBundle {
+    /// The weight carried by the bundle.
+    pub fn weight(&self) -> usize {
+        self.shares.len()
+    }
+}
+
+/// The `WeightedSecretSharingRistretto` structure, for weighted threshold
+/// sharing on top of the Feldman split: participant `p` receives
+/// `weights[p - 1]` shares, and any set of participants whose weights add up
+/// to `threshold` can recover the secret.
+pub struct WeightedSecretSharingRistretto {
+    /// the total weight required to recover the secret.
+    pub threshold: usize,
+    /// the weight of each participant.
+    pub weights: Vec<usize>,
+}
+
+impl WeightedSecretSharingRistretto {
+    /// Split the secret into one bundle per participant and add commitments
+    /// (of threshold size).
+    pub fn split(&self, secret: &Scalar) -> (Vec<ShareBundle>, Vec<RistrettoPoint>) {
+        assert!(self.weights.iter().all(|&weight| weight > 0));
+
+        let vss = VerifiableSecretSharingRistretto {
+            threshold: self.threshold,
+            share_amount: self.weights.iter().sum(),
+        };
+        let (shares, commitments) = vss.split(secret);
+        let bundles = (1..=self.weights.len())
+            .map(|participant| ShareBundle {
+                participant,
+                shares: shares[self.share_positions(participant)].to_vec(),
+            })
+            .collect();
+        (bundles, commitments)
+    }
+
+    /// Recover the secret from bundles whose total weight reaches the threshold.
+    ///
+    /// Returns `None` if the total weight is too low, or if a bundle does not
+    /// hold exactly the identifiers assigned to its participant.
+    pub fn recover(&self, bundles: &[ShareBundle]) -> Option<Scalar> {
+        let mut participants = BTreeSet::new();
+        for bundle in bundles {
+            if bundle.participant == 0
+                || bundle.participant > self.weights.len()
+                || !participants.insert(bundle.participant)
+            {
+                return None;
+            }
+            let identifiers: Vec<usize> = bundle.shares.iter().map(|share| share.0).collect();
+            if !self
+                .share_positions(bundle.participant)
+                .map(|i| i + 1)
+                .eq(identifiers)
+            {
+                return None;
+            }
+        }
+        if bundles.iter().map(ShareBundle::weight).sum::<usize>() < self.threshold {
+            return None;
+        }
+
+        let shares: Vec<(usize, Scalar)> = bundles
+            .iter()
+            .flat_map(|bundle| bundle.shares.iter().cloned())
+            .take(self.threshold)
+            .collect();
+        let xs: Vec<usize> = shares.iter().map(|share| share.0).collect();
+        Some(LagrangeCoefficients::new(&xs, Scalar::zero()).interpolate(&shares))
+    }
+
+    /// Verify that every share of a bundle is valid.
+    pub fn verify(bundle: &ShareBundle, commitments: &[RistrettoPoint]) -> bool {
+        VerifiableSecretSharingRistretto::verify_all(&bundle.shares, commitments)
+    }
+
+    /// The positions, in the underlying sharing, of a participant's shares.
+    fn share_positions(&self, participant: usize) -> std::ops::Range<usize> {
+        let offset: usize = self.weights[..participant - 1].iter().sum();
+        offset..offset + self.weights[participant - 1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feldman_vss::new_random;
+
+    #[test]
+    fn test_integration() {
+        let secret = new_random();
+        // An HSM with three votes, two servers with two and two laptops with one.
+        let wss = WeightedSecretSharingRistretto {
+            threshold: 4,
+            weights: vec![3, 2, 2, 1, 1],
+        };
+        let (bundles, commitments) = wss.split(&secret);
+        assert_eq!(bundles[0].weight(), 3);
+        assert!(bundles
+            .iter()
+            .all(|bundle| WeightedSecretSharingRistretto::verify(bundle, &commitments)));
+
+        let hsm_and_laptop = [bundles[0].clone(), bundles[4].clone()];
+        assert_eq!(wss.recover(&hsm_and_laptop), Some(secret));
+        let servers = [bundles[2].clone(), bundles[1].clone()];
+        assert_eq!(wss.recover(&servers), Some(secret));
+
+        let too_light = [bundles[3].clone(), bundles[4].clone(), bundles[1].clone()];
+        assert_eq!(wss.recover(&too_light[..2]), None);
+        assert_eq!(wss.recover(&too_light), Some(secret));
+        let duplicated = [bundles[1].clone(), bundles[1].clone()];
+        assert_eq!(wss.recover(&duplicated), None);
+    }
+}